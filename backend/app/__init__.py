@@ -1,10 +1,12 @@
+import hmac
 from contextlib import asynccontextmanager
 
-from fastapi import FastAPI
+from fastapi import FastAPI, Request
 from fastapi.middleware.cors import CORSMiddleware
+from fastapi.responses import JSONResponse
 
 from app.config import settings
-from app.db import init_all_databases
+from app.db import close_all_databases, init_all_databases
 
 
 @asynccontextmanager
@@ -16,12 +18,26 @@ async def lifespan(app: FastAPI):
     await recover_stuck_documents()
     yield
 
+    from app.services.task_registry import cancel_all_tasks
+
+    await cancel_all_tasks()
+    close_all_databases()
+
 
 def create_app() -> FastAPI:
     application = FastAPI(
         title="PageNode Backend", version="0.1.0", lifespan=lifespan
     )
 
+    # Registered before CORS so CORS stays outermost and 401s carry its headers.
+    @application.middleware("http")
+    async def require_api_token(request: Request, call_next):
+        if settings.api_token and request.method != "OPTIONS":
+            supplied = request.headers.get("x-pagenode-token", "")
+            if not hmac.compare_digest(supplied, settings.api_token):
+                return JSONResponse({"detail": "Invalid API token"}, status_code=401)
+        return await call_next(request)
+
     application.add_middleware(
         CORSMiddleware,
         allow_origins=["*"],
@@ -30,7 +46,7 @@ def create_app() -> FastAPI:
         allow_headers=["*"],
     )
 
-    from app.routers import documents, graph, health, quiz, setup, upload
+    from app.routers import documents, graph, health, notes, quiz, setup, upload
 
     application.include_router(health.router)
     application.include_router(
@@ -48,6 +64,9 @@ def create_app() -> FastAPI:
     application.include_router(
         quiz.router, prefix="/quiz", tags=["quiz"]
     )
+    application.include_router(
+        notes.router, prefix="/notes", tags=["notes"]
+    )
 
     return application
 
@@ -11,6 +11,8 @@ class Settings(BaseSettings):
     kuzu_dirname: str = "graph"
     files_dirname: str = "files"
     embedding_dim: int = 384  # all-MiniLM-L6-v2 default
+    # Per-launch secret from the Tauri shell; empty disables the check.
+    api_token: str = ""
 
     model_config = {"env_prefix": "PAGENODE_"}
 
@@ -1,7 +1,7 @@
 from pathlib import Path
 
 from app.db.chromadb_ import init_chromadb
-from app.db.kuzu_ import init_kuzu
+from app.db.kuzu_ import close_kuzu, init_kuzu
 from app.db.sqlite import init_sqlite
 
 
@@ -10,3 +10,8 @@ async def init_all_databases(data_dir: Path) -> None:
     await init_sqlite(data_dir)
     init_chromadb(data_dir)
     init_kuzu(data_dir)
+
+
+def close_all_databases() -> None:
+    # SQLite connections are per-request; only the graph DB stays open.
+    close_kuzu()
@@ -71,6 +71,17 @@ def get_kuzu_conn() -> kuzu.Connection:
     return _conn
 
 
+def close_kuzu() -> None:
+    """Release the graph database so its files are flushed and unlocked."""
+    global _db, _conn
+    if _conn is not None:
+        _conn.close()
+    if _db is not None:
+        _db.close()
+    _conn = None
+    _db = None
+
+
 # --- Phase 5: LLM extraction helpers ---
 
 
@@ -8,7 +8,8 @@ import aiosqlite
 from app.config import settings
 from app.models.chunk import Chunk
 from app.models.document import Document, DocumentCreate, DocumentUpdate
-from app.models.flashcard import Flashcard, FlashcardUpdate
+from app.models.flashcard import Flashcard, FlashcardCreate, FlashcardUpdate
+from app.models.note import Note, NoteCreate
 from app.services.chunker import ChunkData
 from app.services.pdf_extractor import TocItem
 
@@ -152,6 +153,43 @@ async def init_sqlite(data_dir: Path) -> None:
                 ALTER TABLE documents ADD COLUMN concept_count INTEGER DEFAULT 0;
                 INSERT OR IGNORE INTO schema_version(version) VALUES (3);
             """)
+        # Migration v3 → v4: FSRS memory state and a review history to fit it to.
+        # Cards SM-2 has scheduled start from its interval and difficulty, the
+        # same mapping as from_sm2 in src-tauri/src/scheduler/fsrs.rs.
+        if current_version < 4:
+            await db.executescript("""
+                ALTER TABLE flashcards ADD COLUMN stability REAL;
+                ALTER TABLE flashcards ADD COLUMN fsrs_difficulty REAL;
+                ALTER TABLE flashcards ADD COLUMN last_review TEXT;
+                CREATE TABLE IF NOT EXISTS review_log (
+                    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                    card_id     TEXT NOT NULL REFERENCES flashcards(id) ON DELETE CASCADE,
+                    grade       INTEGER NOT NULL,
+                    reviewed_on TEXT NOT NULL,
+                    scheduler   TEXT NOT NULL,
+                    created_at  TEXT NOT NULL DEFAULT (datetime('now'))
+                );
+                CREATE INDEX IF NOT EXISTS idx_review_log_card ON review_log(card_id, id);
+                UPDATE flashcards SET
+                    stability = MAX(interval, 1),
+                    fsrs_difficulty = MIN(10.0, MAX(1.0,
+                        1.0 + (COALESCE(difficulty, 0.3) - 0.1) * 11.25)),
+                    last_review = date(next_review, '-' || interval || ' days')
+                  WHERE next_review IS NOT NULL;
+                INSERT OR IGNORE INTO settings(key, value) VALUES ('scheduler', 'sm2');
+                INSERT OR IGNORE INTO schema_version(version) VALUES (4);
+            """)
+        # Migration v4 → v5: ids of reviews the shell has replayed, so a retry
+        # after a lost response doesn't count twice towards mastery.
+        if current_version < 5:
+            await db.executescript("""
+                CREATE TABLE IF NOT EXISTS applied_reviews (
+                    event_id   TEXT PRIMARY KEY,
+                    card_id    TEXT NOT NULL,
+                    applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+                );
+                INSERT OR IGNORE INTO schema_version(version) VALUES (5);
+            """)
         await db.commit()
 
 
@@ -459,10 +497,13 @@ async def update_flashcard_sm2(
     difficulty: float,
     next_review: str,
 ) -> Flashcard | None:
+    """Store an SM-2 result. FSRS state is cleared so the shell rederives it
+    from the new SM-2 state the next time it schedules this card."""
     now = _now()
     await db.execute(
         """UPDATE flashcards
-           SET repetitions = ?, interval = ?, difficulty = ?, next_review = ?, updated_at = ?
+           SET repetitions = ?, interval = ?, difficulty = ?, next_review = ?, updated_at = ?,
+               stability = NULL, fsrs_difficulty = NULL, last_review = NULL
            WHERE id = ?""",
         (repetitions, interval, difficulty, next_review, now, card_id),
     )
@@ -470,6 +511,42 @@ async def update_flashcard_sm2(
     return await get_flashcard(db, card_id)
 
 
+async def log_review(
+    db: aiosqlite.Connection, card_id: str, grade: int, scheduler: str = "sm2"
+) -> None:
+    """Append to review_log, the history FSRS parameters are fitted to."""
+    await db.execute(
+        """INSERT INTO review_log (card_id, grade, reviewed_on, scheduler)
+           VALUES (?, ?, date('now', 'localtime'), ?)""",
+        (card_id, grade, scheduler),
+    )
+    await db.commit()
+
+
+async def claim_review_event(db: aiosqlite.Connection, event_id: str, card_id: str) -> bool:
+    """Record that a journaled review is being applied. False if it already was."""
+    cursor = await db.execute(
+        "INSERT OR IGNORE INTO applied_reviews (event_id, card_id) VALUES (?, ?)",
+        (event_id, card_id),
+    )
+    await db.commit()
+    return cursor.rowcount > 0
+
+
+async def create_flashcard(db: aiosqlite.Connection, card: FlashcardCreate) -> Flashcard:
+    """Insert a hand-written card; next_review stays NULL so it is due right away."""
+    card_id = str(uuid.uuid4())
+    now = _now()
+    await db.execute(
+        """INSERT INTO flashcards
+           (id, document_id, question, answer, created_at, updated_at)
+           VALUES (?, ?, ?, ?, ?, ?)""",
+        (card_id, card.document_id, card.question, card.answer, now, now),
+    )
+    await db.commit()
+    return await get_flashcard(db, card_id)
+
+
 async def update_flashcard_content(
     db: aiosqlite.Connection,
     card_id: str,
@@ -528,3 +605,39 @@ async def get_quiz_stats(db: aiosqlite.Connection) -> dict:
     ]
 
     return {"total_cards": total_cards, "due_today": due_today, "per_doc": per_doc}
+
+
+# --- Notes ---
+
+
+def _row_to_note(row: aiosqlite.Row) -> Note:
+    return Note(**dict(row))
+
+
+async def create_note(db: aiosqlite.Connection, note: NoteCreate) -> Note:
+    note_id = str(uuid.uuid4())
+    now = _now()
+    await db.execute(
+        """INSERT INTO notes (id, document_id, content, created_at, updated_at)
+           VALUES (?, ?, ?, ?, ?)""",
+        (note_id, note.document_id, note.content, now, now),
+    )
+    await db.commit()
+    cursor = await db.execute("SELECT * FROM notes WHERE id = ?", (note_id,))
+    return _row_to_note(await cursor.fetchone())
+
+
+async def list_notes(
+    db: aiosqlite.Connection,
+    doc_id: str | None = None,
+    offset: int = 0,
+    limit: int = 50,
+) -> tuple[list[Note], int]:
+    where, params = ("WHERE document_id = ?", (doc_id,)) if doc_id else ("", ())
+    cursor = await db.execute(f"SELECT COUNT(*) FROM notes {where}", params)
+    total = (await cursor.fetchone())[0]
+    cursor = await db.execute(
+        f"SELECT * FROM notes {where} ORDER BY created_at DESC LIMIT ? OFFSET ?",
+        (*params, limit, offset),
+    )
+    return [_row_to_note(r) for r in await cursor.fetchall()], total
@@ -1,6 +1,6 @@
 from enum import Enum
 
-from pydantic import BaseModel
+from pydantic import BaseModel, Field
 
 
 class FileType(str, Enum):
@@ -49,6 +49,13 @@ class DocumentCreate(BaseModel):
     cover_texture: CoverTexture = CoverTexture.PLAIN
 
 
+class LocalIngest(BaseModel):
+    """A file on the user's machine, hashed (SHA-256) by the desktop shell."""
+
+    path: str
+    file_hash: str = Field(pattern=r"^[0-9a-fA-F]{64}$")
+
+
 class DocumentUpdate(BaseModel):
     title: str | None = None
     author: str | None = None
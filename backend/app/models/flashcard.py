@@ -1,6 +1,6 @@
 from __future__ import annotations
 
-from pydantic import BaseModel
+from pydantic import BaseModel, Field
 
 
 class Flashcard(BaseModel):
@@ -22,6 +22,12 @@ class FlashcardList(BaseModel):
     total: int
 
 
+class FlashcardCreate(BaseModel):
+    document_id: str
+    question: str = Field(min_length=1)
+    answer: str = Field(min_length=1)
+
+
 class FlashcardUpdate(BaseModel):
     question: str | None = None
     answer: str | None = None
@@ -29,6 +35,7 @@ class FlashcardUpdate(BaseModel):
 
 class ReviewRequest(BaseModel):
     grade: int  # 0=Again, 1=Hard, 2=Good, 3=Easy
+    event_id: str | None = None  # set by the shell's review journal; makes replays idempotent
 
 
 class ReviewResult(BaseModel):
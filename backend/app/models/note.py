@@ -0,0 +1,22 @@
+from __future__ import annotations
+
+from pydantic import BaseModel, Field
+
+
+class Note(BaseModel):
+    id: str
+    document_id: str | None
+    chunk_id: str | None
+    content: str
+    created_at: str
+    updated_at: str
+
+
+class NoteCreate(BaseModel):
+    content: str = Field(min_length=1)
+    document_id: str | None = None
+
+
+class NoteList(BaseModel):
+    items: list[Note]
+    total: int
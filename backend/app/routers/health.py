@@ -1,4 +1,4 @@
-from fastapi import APIRouter
+from fastapi import APIRouter, HTTPException, Request
 
 router = APIRouter()
 
@@ -6,3 +6,13 @@ router = APIRouter()
 @router.get("/health")
 async def health():
     return {"status": "ok", "version": "0.1.0"}
+
+
+@router.post("/shutdown", status_code=202)
+async def shutdown(request: Request):
+    """Ask uvicorn to exit once in-flight requests finish (sent by the Tauri shell on quit)."""
+    server = getattr(request.app.state, "server", None)
+    if server is None:
+        raise HTTPException(503, "Shutdown not available")
+    server.should_exit = True
+    return {"status": "shutting_down"}
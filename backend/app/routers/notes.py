@@ -0,0 +1,37 @@
+"""
+Notes router.
+
+Endpoints:
+  POST /notes/   — create a note, optionally linked to a document
+  GET  /notes/   — list notes (optionally filtered by doc_id), newest first
+"""
+from __future__ import annotations
+
+import aiosqlite
+from fastapi import APIRouter, Depends, HTTPException, Query
+
+from app.db.sqlite import create_note, get_db, get_document, list_notes
+from app.models.note import Note, NoteCreate, NoteList
+
+router = APIRouter()
+
+
+@router.post("/", response_model=Note, status_code=201)
+async def add_note(
+    body: NoteCreate,
+    db: aiosqlite.Connection = Depends(get_db),
+) -> Note:
+    if body.document_id and not await get_document(db, body.document_id):
+        raise HTTPException(404, "Document not found")
+    return await create_note(db, body)
+
+
+@router.get("/", response_model=NoteList)
+async def get_notes(
+    doc_id: str | None = Query(default=None),
+    offset: int = Query(default=0, ge=0),
+    limit: int = Query(default=50, ge=1, le=200),
+    db: aiosqlite.Connection = Depends(get_db),
+) -> NoteList:
+    items, total = await list_notes(db, doc_id=doc_id, offset=offset, limit=limit)
+    return NoteList(items=items, total=total)
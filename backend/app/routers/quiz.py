@@ -4,7 +4,9 @@ Quiz & Spaced Repetition router.
 Endpoints:
   GET  /quiz/due             — cards due for review today
   POST /quiz/{id}/review     — submit grade, run SM-2, update concept mastery
+  POST /quiz/{id}/mastery    — update concept mastery only (the shell scheduled the card)
   GET  /quiz/cards           — list all cards (optionally filtered by doc_id)
+  POST /quiz/cards           — add a hand-written card to a document
   GET  /quiz/stats           — summary stats (total, due, per-doc)
   GET  /quiz/{id}            — single card
   PATCH/quiz/{id}            — edit question / answer
@@ -20,17 +22,22 @@ from fastapi import APIRouter, Depends, HTTPException, Query
 
 from app.db.kuzu_ import update_concept_mastery_from_chunk
 from app.db.sqlite import (
+    claim_review_event,
+    create_flashcard,
     delete_flashcard,
+    get_document,
     get_due_flashcards,
     get_flashcard,
     get_quiz_stats,
     list_flashcards,
     update_flashcard_content,
+    log_review,
     update_flashcard_sm2,
     get_db,
 )
 from app.models.flashcard import (
     Flashcard,
+    FlashcardCreate,
     FlashcardList,
     FlashcardUpdate,
     ReviewRequest,
@@ -55,6 +62,9 @@ def _compute_sm2(
     """
     Compute SM-2 scheduling values for a reviewed card.
 
+    The desktop shell schedules with its own port of this
+    (`src-tauri/src/scheduler/sm2.rs`); keep the two in step.
+
     Returns (new_repetitions, new_interval, new_difficulty, next_review_date_iso).
     """
     q = _GRADE_QUALITY[grade]
@@ -117,16 +127,9 @@ async def review_card(
     )
     if not updated:
         raise HTTPException(status_code=500, detail="Failed to update flashcard")
+    await log_review(db, card_id, body.grade)
 
-    # Update concept mastery in Kuzu graph (best-effort; never fails the request)
-    if card.chunk_id:
-        delta = _MASTERY_DELTA[body.grade]
-        try:
-            await update_concept_mastery_from_chunk(card.chunk_id, delta)
-        except Exception:
-            logger.warning(
-                "Mastery update failed for chunk %s (card %s)", card.chunk_id, card_id
-            )
+    await _update_mastery(card, body.grade)
 
     return ReviewResult(
         id=card_id,
@@ -137,6 +140,44 @@ async def review_card(
     )
 
 
+@router.post("/{card_id}/mastery", status_code=204)
+async def record_mastery(
+    card_id: str,
+    body: ReviewRequest,
+    db: aiosqlite.Connection = Depends(get_db),
+) -> None:
+    """Apply a review's effect on concept mastery without rescheduling the card.
+
+    The desktop shell runs the scheduler itself (so reviews survive a backend
+    restart) and replays each grade here from its journal, tagged with an
+    event_id; a replay of an id already applied is acknowledged and ignored.
+    """
+    if body.grade not in (0, 1, 2, 3):
+        raise HTTPException(status_code=422, detail="grade must be 0, 1, 2, or 3")
+
+    card = await get_flashcard(db, card_id)
+    if not card:
+        raise HTTPException(status_code=404, detail="Flashcard not found")
+
+    if body.event_id and not await claim_review_event(db, body.event_id, card_id):
+        return
+
+    await _update_mastery(card, body.grade)
+
+
+async def _update_mastery(card: Flashcard, grade: int) -> None:
+    """Update concept mastery in Kuzu graph (best-effort; never fails the request)."""
+    if not card.chunk_id:
+        return
+    delta = _MASTERY_DELTA[grade]
+    try:
+        await update_concept_mastery_from_chunk(card.chunk_id, delta)
+    except Exception:
+        logger.warning(
+            "Mastery update failed for chunk %s (card %s)", card.chunk_id, card.id
+        )
+
+
 @router.get("/cards", response_model=FlashcardList)
 async def list_cards(
     doc_id: str | None = Query(default=None),
@@ -149,6 +190,17 @@ async def list_cards(
     return FlashcardList(items=items, total=total)
 
 
+@router.post("/cards", response_model=Flashcard, status_code=201)
+async def add_card(
+    body: FlashcardCreate,
+    db: aiosqlite.Connection = Depends(get_db),
+) -> Flashcard:
+    """Create a card by hand (e.g. from the capture window); due immediately."""
+    if not await get_document(db, body.document_id):
+        raise HTTPException(404, "Document not found")
+    return await create_flashcard(db, body)
+
+
 @router.get("/stats")
 async def quiz_stats(db: aiosqlite.Connection = Depends(get_db)) -> dict:
     """Return summary statistics: total cards, due today, per-document breakdown."""
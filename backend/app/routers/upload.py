@@ -1,3 +1,4 @@
+import asyncio
 import hashlib
 import uuid
 from pathlib import Path
@@ -14,10 +15,16 @@ from app.db.sqlite import (
     list_chunks_for_document,
 )
 from app.models.chunk import ChunkList
-from app.models.document import Document, DocumentCreate, FileType
+from app.models.document import Document, DocumentCreate, FileType, LocalIngest
 
 router = APIRouter()
 
+# Kept in step with SUPPORTED_EXTENSIONS in src-tauri/src/import.rs, which
+# decides what the desktop app registers to open.
+INGESTIBLE_SUFFIXES = (".pdf",)
+
+COPY_BLOCK_SIZE = 1024 * 1024
+
 
 @router.post("/upload", response_model=Document, status_code=201)
 async def upload_document(
@@ -25,7 +32,7 @@ async def upload_document(
     db: aiosqlite.Connection = Depends(get_db),
 ):
     # Validate file type
-    if not file.filename or not file.filename.lower().endswith(".pdf"):
+    if not file.filename or not file.filename.lower().endswith(INGESTIBLE_SUFFIXES):
         raise HTTPException(400, "Only PDF files are supported")
 
     # Read file content and compute hash
@@ -37,21 +44,84 @@ async def upload_document(
     if existing:
         raise HTTPException(409, f"Duplicate file. Existing document: {existing.id}")
 
-    # Save file to disk
+    dest = _library_path(file.filename)
+    dest.write_bytes(content)
+    return await _create_and_process(db, file.filename, dest, file_hash, len(content))
+
+
+@router.get("/by-hash/{file_hash}", response_model=Document)
+async def get_document_by_hash(
+    file_hash: str,
+    db: aiosqlite.Connection = Depends(get_db),
+):
+    doc = await find_document_by_hash(db, file_hash.lower())
+    if not doc:
+        raise HTTPException(404, "Document not found")
+    return doc
+
+
+@router.post("/ingest-path", response_model=Document, status_code=201)
+async def ingest_local_file(
+    body: LocalIngest,
+    db: aiosqlite.Connection = Depends(get_db),
+):
+    """Ingest a file already on this machine (desktop drag-and-drop).
+
+    The shell has hashed the file, so it's copied into the library on disk
+    instead of being streamed through a multipart upload. The copy is hashed
+    as it's written and refused if it doesn't match, since the file can change
+    between the shell reading it and this copy.
+    """
+    source = Path(body.path)
+    if not source.is_absolute():
+        raise HTTPException(400, "Path must be absolute")
+    if source.suffix.lower() not in INGESTIBLE_SUFFIXES:
+        raise HTTPException(400, "Only PDF files are supported")
+    if not source.is_file():
+        raise HTTPException(404, "File not found")
+
+    file_hash = body.file_hash.lower()
+    existing = await find_document_by_hash(db, file_hash)
+    if existing:
+        raise HTTPException(409, f"Duplicate file. Existing document: {existing.id}")
+
+    dest = _library_path(source.name)
+    copied_hash, file_size = await asyncio.to_thread(_copy_hashed, source, dest)
+    if copied_hash != file_hash:
+        dest.unlink(missing_ok=True)
+        # Not 409: the shell reads that as "already in the library"
+        raise HTTPException(422, "File changed while importing; try again")
+    return await _create_and_process(db, source.name, dest, file_hash, file_size)
+
+
+def _copy_hashed(source: Path, dest: Path) -> tuple[str, int]:
+    """Copy source to dest, returning the SHA-256 and size of what was written."""
+    digest = hashlib.sha256()
+    size = 0
+    with source.open("rb") as src, dest.open("wb") as dst:
+        while block := src.read(COPY_BLOCK_SIZE):
+            digest.update(block)
+            dst.write(block)
+            size += len(block)
+    return digest.hexdigest(), size
+
+
+def _library_path(filename: str) -> Path:
     files_dir = settings.pagenode_data_dir / settings.files_dirname
     files_dir.mkdir(parents=True, exist_ok=True)
-    doc_id = str(uuid.uuid4())
-    ext = Path(file.filename).suffix.lower()
-    dest = files_dir / f"{doc_id}{ext}"
-    dest.write_bytes(content)
+    ext = Path(filename).suffix.lower()
+    return files_dir / f"{uuid.uuid4()}{ext}"
+
 
-    # Create document record
+async def _create_and_process(
+    db: aiosqlite.Connection, filename: str, dest: Path, file_hash: str, file_size: int
+) -> Document:
     doc_create = DocumentCreate(
-        title=Path(file.filename).stem,
+        title=Path(filename).stem,
         file_type=FileType.PDF,
         file_path=str(dest),
         file_hash=file_hash,
-        file_size=len(content),
+        file_size=file_size,
     )
     doc = await create_document(db, doc_create)
 
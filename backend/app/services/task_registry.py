@@ -27,6 +27,14 @@ def is_processing(doc_id: str) -> bool:
     return task is not None and not task.done()
 
 
+async def cancel_all_tasks() -> None:
+    """Cancel in-flight ingestion tasks; they are re-queued on next startup."""
+    tasks = [t for t in _running_tasks.values() if not t.done()]
+    for task in tasks:
+        task.cancel()
+    await asyncio.gather(*tasks, return_exceptions=True)
+
+
 async def recover_stuck_documents() -> None:
     """Re-queue documents stuck in processing state from a previous crash."""
     from app.db.sqlite import find_documents_by_status, get_db
@@ -14,4 +14,9 @@ def find_free_port() -> int:
 if __name__ == "__main__":
     port = find_free_port()
     print(f"PORT={port}", flush=True)
-    uvicorn.run(app, host="127.0.0.1", port=port, log_level="warning")
+    server = uvicorn.Server(
+        uvicorn.Config(app, host="127.0.0.1", port=port, log_level="warning")
+    )
+    # Exposed so POST /shutdown can stop the server gracefully.
+    app.state.server = server
+    server.run()
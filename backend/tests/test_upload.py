@@ -0,0 +1,58 @@
+import asyncio
+import hashlib
+
+import pytest
+from fastapi import HTTPException
+
+from app.config import settings
+from app.models.document import LocalIngest
+from app.routers import upload
+
+PDF = b"%PDF-1.7\n" + b"x" * 4096
+
+
+@pytest.fixture
+def library(tmp_path, monkeypatch):
+    """Point the library at a temp dir with no documents in it yet."""
+
+    async def no_duplicate(db, file_hash):
+        return None
+
+    monkeypatch.setattr(settings, "pagenode_data_dir", tmp_path / "data")
+    monkeypatch.setattr(upload, "find_document_by_hash", no_duplicate)
+    return tmp_path / "data" / settings.files_dirname
+
+
+async def _ingest(source, file_hash):
+    body = LocalIngest(path=str(source), file_hash=file_hash)
+    return await upload.ingest_local_file(body, db=None)
+
+
+def test_ingest_path_refuses_a_file_that_no_longer_matches_its_hash(tmp_path, library):
+    source = tmp_path / "notes.pdf"
+    source.write_bytes(PDF)
+    shell_hash = hashlib.sha256(PDF).hexdigest()
+    source.write_bytes(PDF + b"edited after hashing")
+
+    with pytest.raises(HTTPException) as refused:
+        asyncio.run(_ingest(source, shell_hash))
+
+    assert refused.value.status_code == 422
+    assert list(library.iterdir()) == []
+
+
+def test_ingest_path_records_the_copied_size(tmp_path, library, monkeypatch):
+    source = tmp_path / "notes.pdf"
+    source.write_bytes(PDF)
+    created = {}
+
+    async def create(db, filename, dest, file_hash, file_size):
+        created.update(dest=dest, file_hash=file_hash, file_size=file_size)
+
+    monkeypatch.setattr(upload, "_create_and_process", create)
+    shell_hash = hashlib.sha256(PDF).hexdigest().upper()
+    asyncio.run(_ingest(source, shell_hash))
+
+    assert created["file_hash"] == shell_hash.lower()
+    assert created["file_size"] == len(PDF)
+    assert created["dest"].read_bytes() == PDF
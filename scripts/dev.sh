@@ -26,6 +26,9 @@ VENV="$BACKEND/.venv/bin/python"
 
 TMPOUT=$(mktemp)
 
+# 실행마다 새 API 토큰 — 백엔드와 Tauri 셸이 같은 값을 공유
+export PAGENODE_API_TOKEN=$(head -c 32 /dev/urandom | od -An -tx1 | tr -d ' \n')
+
 echo "[PageNode] Starting backend (auto port)..."
 
 # stdout을 임시 파일로 리다이렉트 — PORT= 줄이 쓰이길 기다림
@@ -38,7 +41,6 @@ cleanup() {
   kill "$MONITOR_PID" 2>/dev/null || true
   kill "$BACKEND_PID" 2>/dev/null || true
   rm -f "$TMPOUT"
-  rm -f "$ROOT/.env.development.local"
 }
 trap cleanup EXIT INT TERM
 
@@ -64,7 +66,7 @@ echo "[PageNode] Backend detected port $BACKEND_PORT (PID $BACKEND_PID)"
 echo "[PageNode] Verifying backend health..."
 HEALTH_OK=""
 for i in $(seq 1 10); do
-  if curl -sf "http://127.0.0.1:$BACKEND_PORT/health" > /dev/null 2>&1; then
+  if curl -sf -H "X-PageNode-Token: $PAGENODE_API_TOKEN" "http://127.0.0.1:$BACKEND_PORT/health" > /dev/null 2>&1; then
     HEALTH_OK="1"
     break
   fi
@@ -98,16 +100,10 @@ echo "[PageNode] Backend health verified on port $BACKEND_PORT"
 ) &
 MONITOR_PID=$!
 
-# Vite가 읽을 .env 파일에 포트 기록 (가장 확실한 방법)
-ENV_FILE="$ROOT/.env.development.local"
-echo "VITE_BACKEND_PORT=$BACKEND_PORT" > "$ENV_FILE"
-echo "[PageNode] Wrote VITE_BACKEND_PORT=$BACKEND_PORT to $ENV_FILE"
-
 # 혹시 이전 Vite가 1420에서 돌고 있으면 종료
 lsof -ti:1420 2>/dev/null | xargs kill -9 2>/dev/null || true
 
 echo "[PageNode] Starting Tauri dev..."
 cd "$ROOT"
 export PAGENODE_BACKEND_PORT="$BACKEND_PORT"
-export VITE_BACKEND_PORT="$BACKEND_PORT"
 npm run tauri dev
@@ -0,0 +1,644 @@
+//! Whole-library backup and restore.
+//!
+//! A backup is a zip of the four stores under `~/.pagenode/data/`:
+//! `pagenode.db`, `chroma/`, `graph/` and `files/`, each under `data/`, plus
+//! `manifest.json` recording the format version and every file's size and
+//! SHA-256. The backend is paused for the duration (see [`sidecar::pause`]) so
+//! Kuzu and ChromaDB are closed and consistent with each other; SQLite is still
+//! copied through its online backup API since the shell writes to it itself.
+//!
+//! Restoring extracts into a staging directory next to `data/`, checking every
+//! file against the manifest and SQLite's own integrity check, and asks before
+//! swapping it in. Nothing in the live library is touched until then.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tokio::sync::oneshot;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{db, logs, paths, sidecar};
+
+/// `format` of every PageNode backup manifest.
+const FORMAT: &str = "pagenode-backup";
+/// Bumped whenever the archive layout changes; newer backups are refused.
+const MANIFEST_VERSION: u32 = 1;
+const MANIFEST_NAME: &str = "manifest.json";
+/// Archive directory the data files live under.
+const DATA_PREFIX: &str = "data/";
+/// The stores under the data directory, besides `pagenode.db`.
+const STORE_DIRS: &[&str] = &["chroma", "graph", "files"];
+/// Pages copied per step of the SQLite online backup.
+const BACKUP_PAGES_PER_STEP: i32 = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub version: u32,
+    pub app_version: String,
+    /// Local time, RFC 3339.
+    pub created_at: String,
+    /// `MAX(version)` of the backed-up database's `schema_version`.
+    pub schema_version: i64,
+    pub documents: u64,
+    pub flashcards: u64,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Relative to the data directory, `/`-separated.
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// What a restore brought back, for the settings page.
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreSummary {
+    pub created_at: String,
+    pub documents: u64,
+    pub flashcards: u64,
+}
+
+/// Ask where to save, then write a backup there. `None` if the user cancels.
+#[tauri::command]
+pub async fn create_backup(app: AppHandle) -> Result<Option<String>, String> {
+    let default_name = format!(
+        "pagenode-backup-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let (tx, rx) = oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Back up library")
+        .set_file_name(default_name)
+        .add_filter("PageNode backup", &["zip"])
+        .save_file(move |path| {
+            let _ = tx.send(path);
+        });
+    let Some(dest) = rx.await.map_err(|err| err.to_string())? else {
+        return Ok(None);
+    };
+    let dest = dest.into_path().map_err(|err| err.to_string())?;
+    let data_dir = paths::data_dir(&app).map_err(|err| err.to_string())?;
+    let app_version = app.package_info().version.to_string();
+
+    let paused = sidecar::pause(&app).await?;
+    let out = dest.clone();
+    let written =
+        tauri::async_runtime::spawn_blocking(move || write_backup(&data_dir, &out, app_version))
+            .await
+            .map_err(|err| err.to_string())?;
+    drop(paused);
+
+    written.map_err(|err| format!("Backup failed: {err}"))?;
+    logs::shell(&app, &format!("library backed up to {}", dest.display()));
+    Ok(Some(dest.display().to_string()))
+}
+
+/// Ask for a backup, verify it, confirm, and replace the library with it.
+/// `None` if the user cancels at either step.
+#[tauri::command]
+pub async fn restore_backup(app: AppHandle) -> Result<Option<RestoreSummary>, String> {
+    let (tx, rx) = oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Restore library")
+        .add_filter("PageNode backup", &["zip"])
+        .pick_file(move |path| {
+            let _ = tx.send(path);
+        });
+    let Some(source) = rx.await.map_err(|err| err.to_string())? else {
+        return Ok(None);
+    };
+    let source = source.into_path().map_err(|err| err.to_string())?;
+
+    let home = paths::pagenode_home(&app).map_err(|err| err.to_string())?;
+    let data_dir = paths::data_dir(&app).map_err(|err| err.to_string())?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let staging = home.join(format!("data.restore-{stamp}"));
+
+    let staged = staging.clone();
+    let extracted =
+        tauri::async_runtime::spawn_blocking(move || extract_verified(&source, &staged))
+            .await
+            .map_err(|err| err.to_string())?;
+    let manifest = match extracted {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(format!("This backup can't be restored: {err}"));
+        }
+    };
+
+    let question = format!(
+        "Replace your library with the backup from {} ({} documents, {} flashcards)? \
+         Everything added since will be lost.",
+        manifest.created_at, manifest.documents, manifest.flashcards
+    );
+    if !confirm(&app, question).await {
+        let _ = fs::remove_dir_all(&staging);
+        return Ok(None);
+    }
+
+    let paused = sidecar::pause(&app).await?;
+    let replaced = home.join(format!("data.replaced-{stamp}"));
+    let swapped = swap_in(&staging, &data_dir, &replaced);
+    drop(paused);
+    if let Err(err) = swapped {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("Restore failed: {err}"));
+    }
+    if let Err(err) = fs::remove_dir_all(&replaced) {
+        logs::shell(
+            &app,
+            &format!("failed to remove {}: {err}", replaced.display()),
+        );
+    }
+
+    logs::shell(
+        &app,
+        &format!("library restored from backup of {}", manifest.created_at),
+    );
+    Ok(Some(RestoreSummary {
+        created_at: manifest.created_at,
+        documents: manifest.documents,
+        flashcards: manifest.flashcards,
+    }))
+}
+
+async fn confirm(app: &AppHandle, message: String) -> bool {
+    let (tx, rx) = oneshot::channel();
+    app.dialog()
+        .message(message)
+        .title("Restore backup")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Restore".into(),
+            "Cancel".into(),
+        ))
+        .show(move |ok| {
+            let _ = tx.send(ok);
+        });
+    rx.await.unwrap_or(false)
+}
+
+/// Write the archive to a `.partial` file first, so a failed backup never
+/// leaves something that looks complete.
+fn write_backup(data_dir: &Path, dest: &Path, app_version: String) -> Result<(), String> {
+    let partial = dest.with_extension("zip.partial");
+    let snapshot = dest.with_extension("db.partial");
+    let result = write_archive(data_dir, &partial, &snapshot, app_version)
+        .and_then(|()| fs::rename(&partial, dest).map_err(|err| err.to_string()));
+    let _ = fs::remove_file(&snapshot);
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result
+}
+
+fn write_archive(
+    data_dir: &Path,
+    archive: &Path,
+    snapshot: &Path,
+    app_version: String,
+) -> Result<(), String> {
+    let db_path = data_dir.join(paths::SQLITE_FILENAME);
+    if !db_path.is_file() {
+        return Err("there is no library to back up yet".to_string());
+    }
+    snapshot_sqlite(&db_path, snapshot)?;
+    let (schema_version, documents, flashcards) = library_counts(snapshot)?;
+
+    let file = File::create(archive)
+        .map_err(|err| format!("cannot create {}: {err}", archive.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let mut files = vec![add_file(
+        &mut zip,
+        snapshot,
+        paths::SQLITE_FILENAME,
+        CompressionMethod::Deflated,
+    )?];
+    for store in STORE_DIRS {
+        // Imported PDFs and vector data don't shrink; don't spend time trying.
+        let method = if *store == "graph" {
+            CompressionMethod::Deflated
+        } else {
+            CompressionMethod::Stored
+        };
+        for (source, relative) in store_files(data_dir, store)? {
+            files.push(add_file(&mut zip, &source, &relative, method)?);
+        }
+    }
+
+    let manifest = Manifest {
+        format: FORMAT.to_string(),
+        version: MANIFEST_VERSION,
+        app_version,
+        created_at: chrono::Local::now().to_rfc3339(),
+        schema_version,
+        documents,
+        flashcards,
+        files,
+    };
+    let json = serde_json::to_vec_pretty(&manifest).map_err(|err| err.to_string())?;
+    zip.start_file(MANIFEST_NAME, SimpleFileOptions::default())
+        .map_err(|err| err.to_string())?;
+    zip.write_all(&json).map_err(|err| err.to_string())?;
+    let file = zip.finish().map_err(|err| err.to_string())?;
+    file.sync_all().map_err(|err| err.to_string())
+}
+
+/// Copy the database through SQLite's online backup API, which also picks up
+/// anything still in the WAL.
+fn snapshot_sqlite(db_path: &Path, snapshot: &Path) -> Result<(), String> {
+    let source = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|err| format!("cannot open {}: {err}", db_path.display()))?;
+    let _ = fs::remove_file(snapshot);
+    let mut target = Connection::open(snapshot)
+        .map_err(|err| format!("cannot create {}: {err}", snapshot.display()))?;
+    Backup::new(&source, &mut target)
+        .and_then(|backup| backup.run_to_completion(BACKUP_PAGES_PER_STEP, Duration::ZERO, None))
+        .map_err(|err| format!("SQLite backup failed: {err}"))
+}
+
+/// `MAX(schema_version)`, documents and flashcards in a database copy.
+fn library_counts(db: &Path) -> Result<(i64, u64, u64), String> {
+    let conn = Connection::open_with_flags(db, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|err| err.to_string())?;
+    let read = || -> rusqlite::Result<(i64, u64, u64)> {
+        let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, u64>(0));
+        Ok((
+            conn.query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+                [],
+                |row| row.get(0),
+            )?,
+            count("SELECT COUNT(*) FROM documents")?,
+            count("SELECT COUNT(*) FROM flashcards")?,
+        ))
+    };
+    read().map_err(|err| err.to_string())
+}
+
+/// Every regular file of one store, with its manifest path. A store that
+/// doesn't exist yet contributes nothing.
+fn store_files(data_dir: &Path, store: &str) -> Result<Vec<(PathBuf, String)>, String> {
+    let mut found = Vec::new();
+    let mut pending = vec![(data_dir.join(store), store.to_string())];
+    while let Some((path, relative)) = pending.pop() {
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_file() {
+            found.push((path, relative));
+        } else if meta.is_dir() {
+            let entries = fs::read_dir(&path)
+                .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+            for entry in entries {
+                let entry = entry.map_err(|err| err.to_string())?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                pending.push((entry.path(), format!("{relative}/{name}")));
+            }
+        }
+    }
+    found.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(found)
+}
+
+fn add_file(
+    zip: &mut ZipWriter<File>,
+    source: &Path,
+    relative: &str,
+    method: CompressionMethod,
+) -> Result<ManifestFile, String> {
+    let mut file =
+        File::open(source).map_err(|err| format!("cannot read {}: {err}", source.display()))?;
+    let size = file.metadata().map_err(|err| err.to_string())?.len();
+    let options = SimpleFileOptions::default()
+        .compression_method(method)
+        .large_file(size >= u64::from(u32::MAX));
+    zip.start_file(format!("{DATA_PREFIX}{relative}"), options)
+        .map_err(|err| err.to_string())?;
+    let mut hashing = Hashing::new(zip);
+    io::copy(&mut file, &mut hashing)
+        .map_err(|err| format!("cannot archive {}: {err}", source.display()))?;
+    Ok(ManifestFile {
+        path: relative.to_string(),
+        size: hashing.size,
+        sha256: hashing.finish(),
+    })
+}
+
+/// Unpack `archive` into `staging`, failing on anything that doesn't match
+/// its manifest or a database SQLite itself finds damaged.
+fn extract_verified(archive: &Path, staging: &Path) -> Result<Manifest, String> {
+    let file =
+        File::open(archive).map_err(|err| format!("cannot open {}: {err}", archive.display()))?;
+    let mut zip = ZipArchive::new(file).map_err(|err| format!("not a zip archive: {err}"))?;
+
+    let manifest: Manifest = {
+        let mut entry = zip
+            .by_name(MANIFEST_NAME)
+            .map_err(|_| "it has no manifest, so it isn't a PageNode backup".to_string())?;
+        let mut json = Vec::new();
+        entry
+            .read_to_end(&mut json)
+            .map_err(|err| err.to_string())?;
+        serde_json::from_slice(&json).map_err(|err| format!("unreadable manifest: {err}"))?
+    };
+    check_manifest(&manifest)?;
+
+    fs::create_dir_all(staging)
+        .map_err(|err| format!("cannot create {}: {err}", staging.display()))?;
+    for expected in &manifest.files {
+        let target = staging.join(safe_relative_path(&expected.path)?);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let mut entry = zip
+            .by_name(&format!("{DATA_PREFIX}{}", expected.path))
+            .map_err(|_| format!("{} is missing", expected.path))?;
+        let out = File::create(&target)
+            .map_err(|err| format!("cannot write {}: {err}", target.display()))?;
+        let mut hashing = Hashing::new(out);
+        io::copy(&mut entry, &mut hashing)
+            .map_err(|err| format!("cannot extract {}: {err}", expected.path))?;
+        let size = hashing.size;
+        if size != expected.size || hashing.finish() != expected.sha256 {
+            return Err(format!(
+                "{} doesn't match its checksum; the archive is damaged",
+                expected.path
+            ));
+        }
+    }
+
+    let conn = Connection::open_with_flags(
+        staging.join(paths::SQLITE_FILENAME),
+        OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )
+    .map_err(|err| format!("cannot open the backed-up database: {err}"))?;
+    let check: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|err| err.to_string())?;
+    if check != "ok" {
+        return Err(format!("the backed-up database is damaged: {check}"));
+    }
+    Ok(manifest)
+}
+
+fn check_manifest(manifest: &Manifest) -> Result<(), String> {
+    if manifest.format != FORMAT {
+        return Err("it isn't a PageNode backup".to_string());
+    }
+    if manifest.version > MANIFEST_VERSION {
+        return Err(format!(
+            "it was made by a newer PageNode ({}); update first",
+            manifest.app_version
+        ));
+    }
+    // The backend only migrates forwards; an older one can't open it.
+    if manifest.schema_version > db::SCHEMA_VERSION {
+        return Err(format!(
+            "its library comes from a newer PageNode ({}); update first",
+            manifest.app_version
+        ));
+    }
+    if !manifest
+        .files
+        .iter()
+        .any(|file| file.path == paths::SQLITE_FILENAME)
+    {
+        return Err(format!("it has no {}", paths::SQLITE_FILENAME));
+    }
+    Ok(())
+}
+
+/// A manifest path as a relative path inside one of the stores, so a crafted
+/// archive can't write outside the staging directory.
+fn safe_relative_path(path: &str) -> Result<PathBuf, String> {
+    let relative = PathBuf::from(path);
+    let mut components = relative.components();
+    let inside_store = match components.next() {
+        Some(Component::Normal(first)) => {
+            let first = first.to_string_lossy();
+            if first == paths::SQLITE_FILENAME {
+                components.next().is_none()
+            } else {
+                STORE_DIRS.contains(&first.as_ref())
+            }
+        }
+        _ => false,
+    };
+    if !inside_store || !components.all(|part| matches!(part, Component::Normal(_))) {
+        return Err(format!("unexpected path {path:?} in manifest"));
+    }
+    Ok(relative)
+}
+
+/// Move `data_dir` aside to `replaced` and `staging` into its place, putting
+/// the original back if the second move fails.
+fn swap_in(staging: &Path, data_dir: &Path, replaced: &Path) -> Result<(), String> {
+    let had_data = data_dir.exists();
+    if had_data {
+        fs::rename(data_dir, replaced)
+            .map_err(|err| format!("cannot move {} aside: {err}", data_dir.display()))?;
+    }
+    if let Err(err) = fs::rename(staging, data_dir) {
+        if had_data {
+            let _ = fs::rename(replaced, data_dir);
+        }
+        return Err(format!("cannot move the restored library in place: {err}"));
+    }
+    Ok(())
+}
+
+/// Passes writes through while hashing and counting them.
+struct Hashing<W> {
+    inner: W,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl<W> Hashing<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            size: 0,
+        }
+    }
+
+    fn finish(self) -> String {
+        self.hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
+
+impl<W: Write> Write for Hashing<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small library: a database at `schema_version` with one document and
+    /// two flashcards, plus a file in each store.
+    fn library(data_dir: &Path, schema_version: i64) {
+        fs::create_dir_all(data_dir).unwrap();
+        let conn = Connection::open(data_dir.join(paths::SQLITE_FILENAME)).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE schema_version (version INTEGER);
+             CREATE TABLE documents (id TEXT);
+             CREATE TABLE flashcards (id TEXT);
+             INSERT INTO documents VALUES ('d1');
+             INSERT INTO flashcards VALUES ('c1'), ('c2');",
+        )
+        .unwrap();
+        conn.execute("INSERT INTO schema_version VALUES (?1)", [schema_version])
+            .unwrap();
+        for (path, contents) in [
+            ("chroma/chroma.sqlite3", &b"vectors"[..]),
+            ("graph/catalog/0.kz", b"nodes and edges"),
+            ("files/paper.pdf", b"%PDF-1.7 not really"),
+        ] {
+            let path = data_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    /// Copy `from` to `to`, replacing the contents of archive entry `name`.
+    fn tamper(from: &Path, to: &Path, name: &str, contents: &[u8]) {
+        let mut source = ZipArchive::new(File::open(from).unwrap()).unwrap();
+        let mut zip = ZipWriter::new(File::create(to).unwrap());
+        for i in 0..source.len() {
+            let mut entry = source.by_index(i).unwrap();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            zip.start_file(entry.name(), SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(if entry.name() == name {
+                contents
+            } else {
+                &data
+            })
+            .unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn backup_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("data");
+        library(&data_dir, db::SCHEMA_VERSION);
+        let archive = dir.path().join("backup.zip");
+        write_backup(&data_dir, &archive, "1.2.3".to_string()).unwrap();
+        assert!(!archive.with_extension("zip.partial").exists());
+
+        let staging = dir.path().join("staging");
+        let manifest = extract_verified(&archive, &staging).unwrap();
+        assert_eq!(manifest.app_version, "1.2.3");
+        assert_eq!(manifest.schema_version, db::SCHEMA_VERSION);
+        assert_eq!((manifest.documents, manifest.flashcards), (1, 2));
+        assert_eq!(manifest.files.len(), 4);
+        for store in STORE_DIRS {
+            for (source, relative) in store_files(&data_dir, store).unwrap() {
+                assert_eq!(
+                    fs::read(source).unwrap(),
+                    fs::read(staging.join(&relative)).unwrap(),
+                    "{relative}"
+                );
+            }
+        }
+        let restored = staging.join(paths::SQLITE_FILENAME);
+        assert_eq!(
+            library_counts(&restored).unwrap(),
+            (db::SCHEMA_VERSION, 1, 2)
+        );
+    }
+
+    #[test]
+    fn altered_file_fails_its_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("data");
+        library(&data_dir, db::SCHEMA_VERSION);
+        let archive = dir.path().join("backup.zip");
+        write_backup(&data_dir, &archive, "1.2.3".to_string()).unwrap();
+
+        // Same length, different bytes, so only the hash can tell.
+        let damaged = dir.path().join("damaged.zip");
+        tamper(
+            &archive,
+            &damaged,
+            "data/files/paper.pdf",
+            b"%PDF-1.7 NOT really",
+        );
+        let err = extract_verified(&damaged, &dir.path().join("staging")).unwrap_err();
+        assert!(err.contains("files/paper.pdf"), "{err}");
+        assert!(err.contains("checksum"), "{err}");
+    }
+
+    #[test]
+    fn newer_schema_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("data");
+        library(&data_dir, db::SCHEMA_VERSION + 1);
+        let archive = dir.path().join("backup.zip");
+        write_backup(&data_dir, &archive, "9.0.0".to_string()).unwrap();
+
+        let staging = dir.path().join("staging");
+        let err = extract_verified(&archive, &staging).unwrap_err();
+        assert!(err.contains("newer PageNode (9.0.0)"), "{err}");
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn failed_backup_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("backup.zip");
+        assert!(write_backup(&dir.path().join("data"), &archive, "1.2.3".to_string()).is_err());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn manifest_paths_stay_inside_the_stores() {
+        assert!(safe_relative_path("pagenode.db").is_ok());
+        assert!(safe_relative_path("files/abc/paper.pdf").is_ok());
+        assert!(safe_relative_path("graph").is_ok());
+        for path in [
+            "../pagenode.db",
+            "/etc/passwd",
+            "files/../../shell.json",
+            "models/llm.gguf",
+            "pagenode.db/extra",
+            "",
+        ] {
+            assert!(safe_relative_path(path).is_err(), "{path}");
+        }
+    }
+}
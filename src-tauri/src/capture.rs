@@ -0,0 +1,141 @@
+//! Global capture hotkey.
+//!
+//! Pressing the shortcut from [`ShellPrefs::capture_shortcut`] anywhere grabs
+//! the primary selection (Linux) or the clipboard and opens a small
+//! always-on-top window where it can be saved as a note or a draft flashcard.
+//! The window itself posts to `/notes/` or `/quiz/cards` through the
+//! `pagenode-api` proxy; the shell only hands it the text.
+//!
+//! [`ShellPrefs::capture_shortcut`]: crate::prefs::ShellPrefs::capture_shortcut
+
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Listener, Manager, State, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::logs;
+use crate::prefs::{Prefs, ShellPrefs, PREFS_EVENT};
+
+pub const CAPTURE_LABEL: &str = "capture";
+/// Sent to an already open capture window with the newly grabbed text.
+pub const CAPTURE_EVENT: &str = "capture-text";
+
+/// The text waiting for the capture window, and the shortcut we hold.
+#[derive(Default)]
+pub struct Capture {
+    text: Mutex<String>,
+    shortcut: Mutex<Option<Shortcut>>,
+}
+
+pub fn init(app: &AppHandle) {
+    register(app, &app.state::<Prefs>().get().capture_shortcut);
+
+    let handle = app.clone();
+    app.listen(PREFS_EVENT, move |event| {
+        if let Ok(prefs) = serde_json::from_str::<ShellPrefs>(event.payload()) {
+            register(&handle, &prefs.capture_shortcut);
+        }
+    });
+}
+
+/// Swap the registered shortcut for `accelerator` (empty disables capture).
+fn register(app: &AppHandle, accelerator: &str) {
+    let next = match accelerator.trim() {
+        "" => None,
+        text => match text.parse::<Shortcut>() {
+            Ok(shortcut) => Some(shortcut),
+            Err(err) => {
+                logs::shell(app, &format!("invalid capture shortcut {text:?}: {err}"));
+                return;
+            }
+        },
+    };
+
+    let state = app.state::<Capture>();
+    let mut current = state.shortcut.lock().unwrap();
+    if *current == next {
+        return;
+    }
+    if let Some(old) = current.take() {
+        let _ = app.global_shortcut().unregister(old);
+    }
+    let Some(shortcut) = next else {
+        return;
+    };
+    let registered = app
+        .global_shortcut()
+        .on_shortcut(shortcut, |app, _, event| {
+            if event.state == ShortcutState::Pressed {
+                open(app);
+            }
+        });
+    match registered {
+        Ok(()) => *current = Some(shortcut),
+        // Usually another app already owns the combination.
+        Err(err) => logs::shell(app, &format!("cannot register {accelerator}: {err}")),
+    }
+}
+
+/// The user's current selection, falling back to the clipboard.
+fn read_selection() -> Result<String, String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|err| err.to_string())?;
+
+    #[cfg(target_os = "linux")]
+    {
+        use arboard::{GetExtLinux, LinuxClipboardKind};
+        if let Ok(text) = clipboard
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text()
+        {
+            if !text.trim().is_empty() {
+                return Ok(text);
+            }
+        }
+    }
+
+    clipboard.get_text().map_err(|err| err.to_string())
+}
+
+fn open(app: &AppHandle) {
+    let text = read_selection().unwrap_or_else(|err| {
+        logs::shell(app, &format!("nothing to capture: {err}"));
+        String::new()
+    });
+    *app.state::<Capture>().text.lock().unwrap() = text.clone();
+
+    if let Some(window) = app.get_webview_window(CAPTURE_LABEL) {
+        let _ = window.emit_to(CAPTURE_LABEL, CAPTURE_EVENT, text);
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+
+    let built = WebviewWindowBuilder::new(
+        app,
+        CAPTURE_LABEL,
+        WebviewUrl::App("index.html#/capture".into()),
+    )
+    .title("Capture to PageNode")
+    .inner_size(440.0, 420.0)
+    .resizable(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .center()
+    .focused(true)
+    .build();
+    match built {
+        // The app menu bar would take up half of this small window on
+        // Windows and Linux.
+        Ok(window) => {
+            let _ = window.remove_menu();
+        }
+        Err(err) => logs::shell(app, &format!("failed to open capture window: {err}")),
+    }
+}
+
+/// Text grabbed by the last shortcut press, read once by the capture window.
+#[tauri::command]
+pub fn get_capture_text(state: State<Capture>) -> String {
+    state.text.lock().unwrap().clone()
+}
@@ -0,0 +1,73 @@
+//! The backend's SQLite database (`~/.pagenode/data/pagenode.db`), opened by
+//! the shell itself for work that must not wait on the sidecar.
+//!
+//! The backend owns the schema and runs the database in WAL mode, so the shell
+//! can use it alongside; a writer waits up to [`BUSY_TIMEOUT`] for the
+//! backend's transactions to finish.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rusqlite::{Connection, OpenFlags};
+use tauri::AppHandle;
+
+use crate::paths;
+
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// The newest migration in `backend/app/db/sqlite.py` that this build's
+/// backend applies.
+pub const SCHEMA_VERSION: i64 = 5;
+
+pub fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    paths::data_dir(app)
+        .map(|dir| dir.join(paths::SQLITE_FILENAME))
+        .map_err(|err| err.to_string())
+}
+
+/// Open for reading only, alongside a running backend. WAL readers see the
+/// last committed state and never block its writers.
+pub fn open_read_only(path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|err| format!("cannot open {}: {err}", path.display()))?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|err| err.to_string())?;
+    Ok(conn)
+}
+
+/// Open for reading and writing. Never creates the file: until the backend
+/// has run once there is no schema to write to.
+pub fn open_read_write(path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|err| format!("cannot open {}: {err}", path.display()))?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|err| err.to_string())?;
+    Ok(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_version_is_the_backends_latest_migration() {
+        let source = include_str!("../../backend/app/db/sqlite.py");
+        let latest = source
+            .lines()
+            .filter_map(|line| {
+                line.trim()
+                    .strip_prefix("INSERT OR IGNORE INTO schema_version(version) VALUES (")?
+                    .split(')')
+                    .next()?
+                    .parse::<i64>()
+                    .ok()
+            })
+            .max();
+        assert_eq!(latest, Some(SCHEMA_VERSION));
+    }
+}
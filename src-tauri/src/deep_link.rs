@@ -0,0 +1,218 @@
+//! `pagenode://` links shared outside the app.
+//!
+//! `pagenode://concept/<id>`, `pagenode://document/<id>?page=<n>` and
+//! `pagenode://quiz/due` are parsed into a [`DeepLink`] and sent to the webview
+//! as a route. macOS delivers them through `RunEvent::Opened`; Windows and
+//! Linux start (or, via the single-instance plugin, poke) the app with the URL
+//! as an argument. The scheme itself is registered by the bundle: `Info.plist`,
+//! the Linux desktop template and the NSIS installer hooks.
+
+use tauri::{AppHandle, Url};
+
+use crate::{instance, logs, ui_events};
+
+pub const SCHEME: &str = "pagenode";
+
+/// Ids are UUIDs today; anything outside this set is rejected rather than
+/// passed into a route.
+const MAX_ID_LEN: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Concept { id: String },
+    Document { id: String, page: Option<u32> },
+    QuizDue,
+}
+
+impl DeepLink {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let url = Url::parse(input.trim()).map_err(|err| format!("invalid URL: {err}"))?;
+        if url.scheme() != SCHEME {
+            return Err(format!("not a {SCHEME}:// link"));
+        }
+        let kind = url.host_str().ok_or("missing link type")?;
+        let segments: Vec<&str> = url
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        match (kind.to_ascii_lowercase().as_str(), segments.as_slice()) {
+            ("concept", [id]) => Ok(Self::Concept { id: valid_id(id)? }),
+            ("document", [id]) => {
+                let page = match url.query_pairs().find(|(key, _)| key == "page") {
+                    Some((_, value)) => Some(valid_page(&value)?),
+                    None => None,
+                };
+                Ok(Self::Document {
+                    id: valid_id(id)?,
+                    page,
+                })
+            }
+            ("quiz", ["due"]) => Ok(Self::QuizDue),
+            ("concept" | "document", _) => Err(format!("expected {SCHEME}://{kind}/<id>")),
+            ("quiz", _) => Err(format!("unknown quiz link: {}", url.path())),
+            (other, _) => Err(format!("unknown link type: {other}")),
+        }
+    }
+
+    /// The frontend route this link opens.
+    pub fn route(&self) -> String {
+        match self {
+            Self::Concept { id } => format!("/graph?concept={id}"),
+            Self::Document { id, page: None } => format!("/?document={id}"),
+            Self::Document {
+                id,
+                page: Some(page),
+            } => format!("/?document={id}&page={page}"),
+            Self::QuizDue => "/quiz?session=due".to_string(),
+        }
+    }
+}
+
+fn valid_id(id: &str) -> Result<String, String> {
+    let ok = !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if ok {
+        Ok(id.to_string())
+    } else {
+        Err(format!("invalid id: {id:?}"))
+    }
+}
+
+fn valid_page(page: &str) -> Result<u32, String> {
+    page.parse::<u32>()
+        .ok()
+        .filter(|page| *page > 0)
+        .ok_or_else(|| format!("invalid page: {page:?}"))
+}
+
+/// Open every `pagenode://` link among `args`; other arguments are ignored.
+pub fn open_args<I>(app: &AppHandle, args: I)
+where
+    I: IntoIterator<Item = String>,
+{
+    let prefix = format!("{SCHEME}:");
+    for arg in args {
+        if arg.to_ascii_lowercase().starts_with(&prefix) {
+            open(app, &arg);
+        }
+    }
+}
+
+/// Focus the app and navigate to `link`, or log why it was refused.
+pub fn open(app: &AppHandle, link: &str) {
+    match DeepLink::parse(link) {
+        Ok(parsed) => {
+            logs::shell(app, &format!("opening {link}"));
+            instance::focus_main_window(app);
+            ui_events::navigate(app, parsed.route());
+        }
+        Err(err) => logs::shell(app, &format!("ignoring deep link {link}: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn concept(id: &str) -> DeepLink {
+        DeepLink::Concept { id: id.to_string() }
+    }
+
+    fn document(id: &str, page: Option<u32>) -> DeepLink {
+        DeepLink::Document {
+            id: id.to_string(),
+            page,
+        }
+    }
+
+    #[test]
+    fn parses_supported_links() {
+        let id = "3f2b8c1e-9a4d-4e57-b1c2-7d0e6f5a4b3c";
+        assert_eq!(
+            DeepLink::parse(&format!("pagenode://concept/{id}")),
+            Ok(concept(id))
+        );
+        assert_eq!(
+            DeepLink::parse(&format!("pagenode://document/{id}")),
+            Ok(document(id, None))
+        );
+        assert_eq!(
+            DeepLink::parse(&format!("pagenode://document/{id}?page=42")),
+            Ok(document(id, Some(42)))
+        );
+        assert_eq!(
+            DeepLink::parse("pagenode://quiz/due"),
+            Ok(DeepLink::QuizDue)
+        );
+    }
+
+    #[test]
+    fn tolerates_trailing_slash_and_case() {
+        assert_eq!(
+            DeepLink::parse("pagenode://concept/abc/"),
+            Ok(concept("abc"))
+        );
+        assert_eq!(
+            DeepLink::parse("PageNode://Quiz/due"),
+            Ok(DeepLink::QuizDue)
+        );
+        assert_eq!(
+            DeepLink::parse("  pagenode://quiz/due\n"),
+            Ok(DeepLink::QuizDue)
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_query_params() {
+        assert_eq!(
+            DeepLink::parse("pagenode://document/abc?utm=chat&page=3"),
+            Ok(document("abc", Some(3)))
+        );
+        assert_eq!(
+            DeepLink::parse("pagenode://concept/abc?page=3"),
+            Ok(concept("abc"))
+        );
+    }
+
+    #[test]
+    fn rejects_other_schemes_and_kinds() {
+        assert!(DeepLink::parse("https://concept/abc").is_err());
+        assert!(DeepLink::parse("pagenode-api://localhost/health").is_err());
+        assert!(DeepLink::parse("pagenode://settings/general").is_err());
+        assert!(DeepLink::parse("pagenode://quiz/all").is_err());
+        assert!(DeepLink::parse("pagenode:concept/abc").is_err());
+        assert!(DeepLink::parse("not a url").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_ids() {
+        assert!(DeepLink::parse("pagenode://concept").is_err());
+        assert!(DeepLink::parse("pagenode://concept/").is_err());
+        assert!(DeepLink::parse("pagenode://concept/a/b").is_err());
+        assert!(DeepLink::parse("pagenode://concept/..%2Fsettings").is_err());
+        assert!(DeepLink::parse("pagenode://document/abc%20def").is_err());
+        let long = "a".repeat(MAX_ID_LEN + 1);
+        assert!(DeepLink::parse(&format!("pagenode://concept/{long}")).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_pages() {
+        for page in ["0", "-1", "abc", "", "99999999999"] {
+            let link = format!("pagenode://document/abc?page={page}");
+            assert!(DeepLink::parse(&link).is_err(), "{link}");
+        }
+    }
+
+    #[test]
+    fn routes() {
+        assert_eq!(concept("abc").route(), "/graph?concept=abc");
+        assert_eq!(document("abc", None).route(), "/?document=abc");
+        assert_eq!(document("abc", Some(7)).route(), "/?document=abc&page=7");
+        assert_eq!(DeepLink::QuizDue.route(), "/quiz?session=due");
+    }
+}
@@ -0,0 +1,254 @@
+//! One-click diagnostics bundle for bug reports.
+//!
+//! `export_diagnostics` asks the user where to save, then writes a zip with
+//! version and OS info, the tail of the shell and backend logs, the SQLite
+//! `schema_version` and `settings` rows, and the size of everything under
+//! `~/.pagenode/data/`. Settings that look like secrets are redacted and the
+//! home directory is replaced with `~` so the archive is safe to attach.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OpenFlags};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+use tokio::sync::oneshot;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::logs::Logs;
+use crate::paths;
+use crate::sidecar::SidecarState;
+
+const RECENT_LOG_LINES: usize = 2000;
+/// Settings whose key contains one of these are never exported verbatim.
+const SECRET_MARKERS: &[&str] = &["token", "secret", "password", "key"];
+
+/// Everything the bundle needs, gathered on the command thread.
+struct Bundle {
+    system: Value,
+    backend_log: Vec<String>,
+    shell_log: Vec<String>,
+    data_dir: PathBuf,
+    home: PathBuf,
+}
+
+#[tauri::command]
+pub async fn export_diagnostics(app: AppHandle) -> Result<Option<String>, String> {
+    let default_name = format!(
+        "pagenode-diagnostics-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let (tx, rx) = oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Export diagnostics")
+        .set_file_name(default_name)
+        .add_filter("Zip archive", &["zip"])
+        .save_file(move |path| {
+            let _ = tx.send(path);
+        });
+    let Some(dest) = rx.await.map_err(|err| err.to_string())? else {
+        return Ok(None);
+    };
+    let dest = dest.into_path().map_err(|err| err.to_string())?;
+
+    let logs = app.state::<Logs>();
+    let bundle = Bundle {
+        system: system_info(&app),
+        backend_log: logs.backend.recent(RECENT_LOG_LINES),
+        shell_log: logs.shell.recent(RECENT_LOG_LINES),
+        data_dir: paths::data_dir(&app).map_err(|err| err.to_string())?,
+        home: app.path().home_dir().map_err(|err| err.to_string())?,
+    };
+
+    let out = dest.clone();
+    tauri::async_runtime::spawn_blocking(move || write_bundle(&out, bundle))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| format!("failed to write {}: {err}", dest.display()))?;
+
+    Ok(Some(dest.display().to_string()))
+}
+
+fn system_info(app: &AppHandle) -> Value {
+    let package = app.package_info();
+    json!({
+        "app_name": package.name,
+        "app_version": package.version.to_string(),
+        "config_version": app.config().version,
+        "tauri_version": tauri::VERSION,
+        "os": std::env::consts::OS,
+        "os_family": std::env::consts::FAMILY,
+        "arch": std::env::consts::ARCH,
+        "backend_state": app.state::<SidecarState>().current(),
+        "generated_at": chrono::Local::now().to_rfc3339(),
+    })
+}
+
+fn write_bundle(dest: &Path, bundle: Bundle) -> zip::result::ZipResult<()> {
+    let home = bundle.home.display().to_string();
+    let scrub = |text: &str| text.replace(&home, "~");
+
+    let mut zip = ZipWriter::new(File::create(dest)?);
+    let options = SimpleFileOptions::default();
+
+    zip.start_file("system.json", options)?;
+    zip.write_all(pretty(&bundle.system).as_bytes())?;
+
+    for (name, lines) in [
+        ("logs/backend.log", &bundle.backend_log),
+        ("logs/shell.log", &bundle.shell_log),
+    ] {
+        zip.start_file(name, options)?;
+        for line in lines {
+            writeln!(zip, "{}", scrub(line))?;
+        }
+    }
+
+    let db = sqlite_snapshot(&bundle.data_dir.join(paths::SQLITE_FILENAME), &home);
+    zip.start_file("sqlite.json", options)?;
+    zip.write_all(pretty(&db).as_bytes())?;
+
+    zip.start_file("data_sizes.json", options)?;
+    zip.write_all(pretty(&data_sizes(&bundle.data_dir)).as_bytes())?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn redact(key: &str, value: &str, home: &str) -> String {
+    let lower = key.to_ascii_lowercase();
+    if SECRET_MARKERS.iter().any(|marker| lower.contains(marker)) && !value.is_empty() {
+        "[redacted]".to_string()
+    } else {
+        value.replace(home, "~")
+    }
+}
+
+/// `schema_version` and redacted `settings`, or the error that prevented it.
+fn sqlite_snapshot(db_path: &Path, home: &str) -> Value {
+    let read = || -> rusqlite::Result<Value> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+        let mut stmt =
+            conn.prepare("SELECT version, applied_at FROM schema_version ORDER BY version")?;
+        let versions = stmt
+            .query_map([], |row| {
+                Ok(json!({
+                    "version": row.get::<_, i64>(0)?,
+                    "applied_at": row.get::<_, String>(1)?,
+                }))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare("SELECT key, value, updated_at FROM settings ORDER BY key")?;
+        let settings = stmt
+            .query_map([], |row| {
+                let key: String = row.get(0)?;
+                let value: String = row.get(1)?;
+                Ok(json!({
+                    "key": key,
+                    "value": redact(&key, &value, home),
+                    "updated_at": row.get::<_, String>(2)?,
+                }))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(json!({ "schema_version": versions, "settings": settings }))
+    };
+    read().unwrap_or_else(|err| json!({ "error": err.to_string() }))
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| dir_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Size in bytes of each top-level entry of the data directory.
+fn data_sizes(data_dir: &Path) -> Value {
+    let mut sizes = serde_json::Map::new();
+    if let Ok(entries) = fs::read_dir(data_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            sizes.insert(name, json!(dir_size(&entry.path())));
+        }
+    }
+    json!({ "total": dir_size(data_dir), "entries": sizes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOME: &str = "/home/ada";
+
+    #[test]
+    fn secret_looking_settings_are_redacted() {
+        for key in [
+            "openai_api_key",
+            "OPENAI_API_KEY",
+            "anthropic_apikey",
+            "hf_token",
+            "api_token",
+            "sync_secret",
+            "proxy_password",
+        ] {
+            assert_eq!(redact(key, "sk-live-123", HOME), "[redacted]", "{key}");
+        }
+    }
+
+    #[test]
+    fn ordinary_settings_are_kept_with_home_replaced() {
+        assert_eq!(redact("setup_complete", "true", HOME), "true");
+        assert_eq!(
+            redact("llm_model_path", "/home/ada/.pagenode/models/a.gguf", HOME),
+            "~/.pagenode/models/a.gguf"
+        );
+        // Nothing to hide, and "[redacted]" would suggest there was.
+        assert_eq!(redact("openai_api_key", "", HOME), "");
+    }
+
+    #[test]
+    fn snapshot_never_contains_secret_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("pagenode.db");
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE schema_version (version INTEGER, applied_at TEXT);
+             INSERT INTO schema_version VALUES (1, '2026-01-01');
+             CREATE TABLE settings (key TEXT, value TEXT, updated_at TEXT);
+             INSERT INTO settings VALUES
+                 ('openai_api_key', 'sk-live-123', '2026-01-01'),
+                 ('hf_token', 'hf_abc', '2026-01-01'),
+                 ('llm_model_id', 'qwen2.5-3b', '2026-01-01');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let snapshot = sqlite_snapshot(&db_path, HOME);
+        let dumped = snapshot.to_string();
+        assert!(!dumped.contains("sk-live-123"));
+        assert!(!dumped.contains("hf_abc"));
+        let values: Vec<&str> = snapshot["settings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|setting| setting["value"].as_str().unwrap())
+            .collect();
+        assert_eq!(values, ["[redacted]", "qwen2.5-3b", "[redacted]"]);
+    }
+}
@@ -0,0 +1,108 @@
+//! Periodic liveness probing of the backend.
+//!
+//! The `PORT=` handshake only proves the sidecar started. This monitor keeps
+//! hitting `/health` on an interval, reports latency and failures to every
+//! window as [`HEALTH_EVENT`], and kills a process that is alive but wedged
+//! (e.g. stuck inside a llama.cpp call) so the supervisor restarts it.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::logs;
+use crate::proxy::LoopbackClient;
+use crate::sidecar::SidecarState;
+use crate::token::{ApiToken, TOKEN_HEADER};
+use crate::BackendPort;
+
+pub const HEALTH_EVENT: &str = "backend-health";
+
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Consecutive failed probes before a live-but-silent backend is restarted.
+const MAX_CONSECUTIVE_FAILURES: u32 = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub latency_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub error: Option<String>,
+}
+
+/// One `GET /health`, returning the round-trip time on success.
+pub async fn probe(
+    client: &reqwest::Client,
+    port: u16,
+    token: &str,
+    timeout: Duration,
+) -> Result<Duration, String> {
+    let started = Instant::now();
+    let response = client
+        .get(format!("http://127.0.0.1:{port}/health"))
+        .header(TOKEN_HEADER, token)
+        .timeout(timeout)
+        .send()
+        .await
+        .map_err(|err| err.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("/health returned {}", response.status()));
+    }
+    Ok(started.elapsed())
+}
+
+/// Start the probe loop on the async runtime.
+pub fn monitor(handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut failures = 0u32;
+        let mut interval = tokio::time::interval(PROBE_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            // Starting or restarting: the supervisor owns that phase.
+            let Some(port) = handle.state::<BackendPort>().get() else {
+                failures = 0;
+                continue;
+            };
+            let client = handle.state::<LoopbackClient>().client().clone();
+            let token = handle.state::<ApiToken>().get();
+
+            let report = match probe(&client, port, &token, PROBE_TIMEOUT).await {
+                Ok(latency) => {
+                    failures = 0;
+                    HealthReport {
+                        healthy: true,
+                        latency_ms: Some(latency.as_millis() as u64),
+                        consecutive_failures: 0,
+                        error: None,
+                    }
+                }
+                Err(error) => {
+                    failures += 1;
+                    HealthReport {
+                        healthy: false,
+                        latency_ms: None,
+                        consecutive_failures: failures,
+                        error: Some(error),
+                    }
+                }
+            };
+            if let Err(err) = handle.emit(HEALTH_EVENT, &report) {
+                logs::shell(&handle, &format!("failed to emit {HEALTH_EVENT}: {err}"));
+            }
+
+            if failures >= MAX_CONSECUTIVE_FAILURES {
+                logs::shell(
+                    &handle,
+                    &format!("backend unresponsive for {failures} probes, restarting it"),
+                );
+                // No-op for an external (dev.sh) backend we don't own.
+                handle.state::<SidecarState>().kill();
+                failures = 0;
+            }
+        }
+    });
+}
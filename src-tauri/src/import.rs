@@ -0,0 +1,393 @@
+//! Documents handed to us by the OS: "Open with PageNode", a file dropped on
+//! the window or the dock icon, or paths on the command line.
+//!
+//! Each path is checked and hashed here, so a file that is already in the
+//! library is turned away before the backend touches it. New files are handed
+//! to the backend's `/documents/ingest-path` as a local path rather than
+//! re-uploaded. The frontend is sent to the library, told about every result
+//! so the modal can open straight at its processing step, and gets
+//! [`PROGRESS_EVENT`] for each file of a multi-file drop.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::http::{header, Method, StatusCode};
+use tauri::{AppHandle, DragDropEvent, Manager, WindowEvent};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::{instance, logs, proxy, ui_events};
+
+/// What the backend can ingest (`INGESTIBLE_SUFFIXES` in
+/// `backend/app/routers/upload.py`), and so all we register for in
+/// `bundle.fileAssociations` and the Linux desktop entry.
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["pdf"];
+
+/// `{ path, document }` — the backend accepted the file.
+pub const IMPORTED_EVENT: &str = "document-imported";
+/// `{ path, error }` — the file was rejected here or by the backend.
+pub const FAILED_EVENT: &str = "import-failed";
+/// `{ path, index, total, stage }` — where each file of a batch has got to.
+pub const PROGRESS_EVENT: &str = "import-progress";
+
+#[derive(Debug, Clone, Serialize)]
+struct Imported {
+    path: String,
+    document: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Failed {
+    path: String,
+    error: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Stage {
+    Queued,
+    Hashing,
+    Ingesting,
+    Done,
+    Duplicate,
+    /// Not a type the backend ingests; turned away before hashing.
+    Unsupported,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Progress<'a> {
+    path: &'a str,
+    index: usize,
+    total: usize,
+    stage: Stage,
+}
+
+enum Rejected {
+    /// Already in the library; carries the message for the user.
+    Duplicate(String),
+    Unsupported(String),
+    Failed(String),
+}
+
+impl From<String> for Rejected {
+    fn from(error: String) -> Self {
+        Rejected::Failed(error)
+    }
+}
+
+/// Import files dropped on the main window.
+pub fn init(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let handle = app.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::DragDrop(DragDropEvent::Drop { paths, .. }) = event {
+            // Unsupported files stay in the batch so the list says why they
+            // were skipped.
+            import_paths(&handle, paths.clone());
+        }
+    });
+}
+
+/// Import every document among `args`, ignoring flags and URLs (deep links
+/// included; [`crate::deep_link`] opens those).
+pub fn import_args<I>(app: &AppHandle, args: I)
+where
+    I: IntoIterator<Item = String>,
+{
+    let paths: Vec<PathBuf> = args
+        .into_iter()
+        .filter(|arg| !arg.starts_with('-') && !has_url_scheme(arg))
+        .map(PathBuf::from)
+        .collect();
+    import_paths(app, paths);
+}
+
+/// Whether `arg` starts with an RFC 3986 scheme and a colon, as in
+/// `pagenode:concept/x` or `https://…`. A single letter is a Windows drive,
+/// not a scheme.
+fn has_url_scheme(arg: &str) -> bool {
+    let Some((scheme, _)) = arg.split_once(':') else {
+        return false;
+    };
+    scheme.len() > 1
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Import `paths` one after another in the background.
+pub fn import_paths(app: &AppHandle, paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+    ui_events::navigate(app, "/");
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let shown: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+        let total = paths.len();
+        let report = |index: usize, stage: Stage| {
+            let progress = Progress {
+                path: &shown[index],
+                index,
+                total,
+                stage,
+            };
+            ui_events::emit(&app, PROGRESS_EVENT, progress);
+        };
+        for index in 0..total {
+            report(index, Stage::Queued);
+        }
+
+        for (index, path) in paths.iter().enumerate() {
+            let shown = shown[index].clone();
+            let stage = match import_one(&app, path, |stage| report(index, stage)).await {
+                Ok(document) => {
+                    logs::shell(&app, &format!("imported {shown}"));
+                    ui_events::emit(
+                        &app,
+                        IMPORTED_EVENT,
+                        Imported {
+                            path: shown,
+                            document,
+                        },
+                    );
+                    Stage::Done
+                }
+                Err(Rejected::Duplicate(error)) => {
+                    ui_events::emit(&app, FAILED_EVENT, Failed { path: shown, error });
+                    Stage::Duplicate
+                }
+                Err(Rejected::Unsupported(error)) => {
+                    ui_events::emit(&app, FAILED_EVENT, Failed { path: shown, error });
+                    Stage::Unsupported
+                }
+                Err(Rejected::Failed(error)) => {
+                    logs::shell(&app, &format!("failed to import {shown}: {error}"));
+                    ui_events::emit(&app, FAILED_EVENT, Failed { path: shown, error });
+                    Stage::Failed
+                }
+            };
+            report(index, stage);
+        }
+    });
+}
+
+/// Let the user choose documents with the native file dialog and import them.
+pub fn pick_documents(app: &AppHandle) {
+    let handle = app.clone();
+    app.dialog()
+        .file()
+        .set_title("Import documents")
+        .add_filter("Documents", SUPPORTED_EXTENSIONS)
+        .pick_files(move |picked| {
+            let paths = picked
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|path| path.into_path().ok())
+                .collect();
+            instance::focus_main_window(&handle);
+            import_paths(&handle, paths);
+        });
+}
+
+async fn import_one(
+    app: &AppHandle,
+    path: &Path,
+    report: impl Fn(Stage),
+) -> Result<Value, Rejected> {
+    let path = validate(path)?;
+
+    report(Stage::Hashing);
+    let file_hash = sha256_file(path.clone()).await?;
+    if let Some(title) = find_by_hash(app, &file_hash).await? {
+        return Err(Rejected::Duplicate(format!(
+            "Already in your library as \"{title}\"."
+        )));
+    }
+
+    report(Stage::Ingesting);
+    let body = json!({ "path": path, "file_hash": file_hash });
+    let response = proxy::backend_request(app, Method::POST, "/documents/ingest-path")
+        .await?
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|err| format!("backend request failed: {err}"))?;
+    let (status, json) = read_json(response).await?;
+
+    match status {
+        StatusCode::CREATED | StatusCode::OK => Ok(json),
+        StatusCode::CONFLICT => Err(Rejected::Duplicate(
+            "This file has already been uploaded.".to_string(),
+        )),
+        _ => Err(Rejected::Failed(error_detail(&json, status))),
+    }
+}
+
+/// Title of the library document with this content hash, if any.
+async fn find_by_hash(app: &AppHandle, file_hash: &str) -> Result<Option<String>, String> {
+    let response =
+        proxy::backend_request(app, Method::GET, &format!("/documents/by-hash/{file_hash}"))
+            .await?
+            .send()
+            .await
+            .map_err(|err| format!("backend request failed: {err}"))?;
+    let (status, json) = read_json(response).await?;
+    match status {
+        StatusCode::OK => Ok(Some(json["title"].as_str().unwrap_or_default().to_string())),
+        StatusCode::NOT_FOUND => Ok(None),
+        _ => Err(error_detail(&json, status)),
+    }
+}
+
+async fn read_json(response: reqwest::Response) -> Result<(StatusCode, Value), String> {
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| format!("failed to read backend response: {err}"))?;
+    Ok((status, serde_json::from_str(&text).unwrap_or(Value::Null)))
+}
+
+fn error_detail(json: &Value, status: StatusCode) -> String {
+    json["detail"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("import failed with {status}"))
+}
+
+/// Hex SHA-256 of the file, streamed so large PDFs aren't held in memory.
+async fn sha256_file(path: PathBuf) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut file = File::open(&path)?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        Ok::<_, std::io::Error>(format!("{:x}", hasher.finalize()))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(|err| format!("cannot read file: {err}"))
+}
+
+/// Resolve `path` and make sure it is an existing file we can ingest.
+fn validate(path: &Path) -> Result<PathBuf, Rejected> {
+    if !is_supported(path) {
+        return Err(Rejected::Unsupported(unsupported_message(path)));
+    }
+    let path = path
+        .canonicalize()
+        .map_err(|err| format!("cannot open file: {err}"))?;
+    if !path.is_file() {
+        return Err(Rejected::Failed("not a file".to_string()));
+    }
+    Ok(path)
+}
+
+fn unsupported_message(path: &Path) -> String {
+    let supported: Vec<String> = SUPPORTED_EXTENSIONS
+        .iter()
+        .map(|ext| format!(".{ext}"))
+        .collect();
+    let supported = supported.join(", ");
+    match extension(path).as_str() {
+        "" => format!("not a supported document; PageNode imports {supported} files"),
+        ext => format!("unsupported file type .{ext}; PageNode imports {supported} files"),
+    }
+}
+
+fn is_supported(path: &Path) -> bool {
+    SUPPORTED_EXTENSIONS.contains(&extension(path).as_str())
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The quoted suffixes in the backend's `INGESTIBLE_SUFFIXES` tuple.
+    fn backend_suffixes() -> Vec<String> {
+        let source = include_str!("../../backend/app/routers/upload.py");
+        let line = source
+            .lines()
+            .find(|line| line.starts_with("INGESTIBLE_SUFFIXES = "))
+            .expect("upload.py defines INGESTIBLE_SUFFIXES");
+        line.split('"')
+            .skip(1)
+            .step_by(2)
+            .map(|suffix| suffix.trim_start_matches('.').to_string())
+            .collect()
+    }
+
+    #[test]
+    fn urls_are_not_paths() {
+        for arg in [
+            "pagenode://concept/abc",
+            "pagenode:concept/abc",
+            "PageNode:quiz/due",
+            "https://example.com/a.pdf",
+            "mailto:someone@example.com",
+        ] {
+            assert!(has_url_scheme(arg), "{arg}");
+        }
+        for arg in [
+            "/home/me/paper.pdf",
+            "C:\\Users\\me\\paper.pdf",
+            "c:/Users/me/paper.pdf",
+            "paper.pdf",
+            "/home/me/notes:v2.pdf",
+            "./draft:1.pdf",
+        ] {
+            assert!(!has_url_scheme(arg), "{arg}");
+        }
+    }
+
+    #[test]
+    fn only_ingestible_files_are_supported() {
+        assert!(is_supported(Path::new("/tmp/Paper.PDF")));
+        for path in ["/tmp/notes.md", "/tmp/report.docx", "/tmp/README"] {
+            assert!(!is_supported(Path::new(path)), "{path}");
+        }
+        assert_eq!(
+            unsupported_message(Path::new("/tmp/notes.md")),
+            "unsupported file type .md; PageNode imports .pdf files"
+        );
+    }
+
+    #[test]
+    fn extensions_match_what_the_backend_ingests() {
+        let mut ours: Vec<_> = SUPPORTED_EXTENSIONS.to_vec();
+        let mut backend = backend_suffixes();
+        ours.sort_unstable();
+        backend.sort_unstable();
+        assert_eq!(ours, backend);
+    }
+
+    #[test]
+    fn file_associations_match_supported_extensions() {
+        let config: Value = serde_json::from_str(include_str!("../tauri.conf.json")).unwrap();
+        let mut declared: Vec<&str> = config["bundle"]["fileAssociations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|association| association["ext"].as_array().unwrap())
+            .map(|ext| ext.as_str().unwrap())
+            .collect();
+        declared.sort_unstable();
+        let mut ours = SUPPORTED_EXTENSIONS.to_vec();
+        ours.sort_unstable();
+        assert_eq!(declared, ours);
+    }
+}
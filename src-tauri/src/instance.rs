@@ -0,0 +1,52 @@
+//! Single-instance enforcement.
+//!
+//! Two PageNode processes would each spawn a sidecar against the same
+//! `~/.pagenode/data/`, and Kuzu and SQLite don't tolerate two writers. A
+//! second launch therefore exits immediately; the running instance focuses its
+//! main window, imports any documents among the second launch's arguments and
+//! opens any `pagenode://` links.
+
+use std::path::Path;
+
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::{deep_link, import};
+
+/// Must be the first plugin registered so it runs before anything else.
+pub fn plugin() -> TauriPlugin<Wry> {
+    tauri_plugin_single_instance::init(|app, argv, cwd| {
+        focus_main_window(app);
+        // Relative paths are relative to the second launch's directory.
+        let args: Vec<String> = argv
+            .into_iter()
+            .skip(1)
+            .map(|arg| absolutize(&arg, &cwd))
+            .collect();
+        deep_link::open_args(app, args.clone());
+        import::import_args(app, args);
+    })
+}
+
+pub fn focus_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+/// Flags pass through untouched; anything that exists relative to `cwd` is
+/// turned into an absolute path.
+fn absolutize(arg: &str, cwd: &str) -> String {
+    if arg.starts_with('-') || Path::new(arg).is_absolute() {
+        return arg.to_string();
+    }
+    let joined = Path::new(cwd).join(arg);
+    if joined.exists() {
+        joined.display().to_string()
+    } else {
+        arg.to_string()
+    }
+}
@@ -1,67 +1,186 @@
-use std::sync::Mutex;
-use tauri::Manager;
-use tauri::State;
-use tauri_plugin_shell::process::CommandEvent;
-use tauri_plugin_shell::ShellExt;
+mod backup;
+mod capture;
+mod db;
+mod deep_link;
+mod diagnostics;
+mod health;
+mod import;
+mod instance;
+mod library;
+mod logs;
+mod menu;
+mod notify;
+mod paths;
+mod port;
+mod prefs;
+mod proxy;
+mod review_queue;
+mod scheduler;
+mod sidecar;
+mod token;
+mod tray;
+mod ui_events;
+mod window_state;
+mod windows;
 
-pub struct BackendPort(pub Mutex<u16>);
+use std::time::Duration;
+use tauri::{Manager, RunEvent, State};
 
+use logs::Logs;
+pub use port::BackendPort;
+use proxy::{BackendStreams, LoopbackClient};
+use sidecar::{BackendState, SidecarState};
+use token::ApiToken;
+use ui_events::UiEvents;
+
+/// How long callers needing the backend wait for the sidecar handshake.
+pub(crate) const PORT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[tauri::command]
+async fn get_backend_port(state: State<'_, BackendPort>) -> Result<u16, String> {
+    state.wait(PORT_WAIT_TIMEOUT).await
+}
+
+#[tauri::command]
+fn get_backend_state(state: State<SidecarState>) -> BackendState {
+    state.current()
+}
+
+/// Last `n` lines of the backend log, oldest first, for the in-app log viewer.
 #[tauri::command]
-fn get_backend_port(state: State<BackendPort>) -> u16 {
-    *state.0.lock().unwrap()
+async fn get_recent_logs(logs: State<'_, Logs>, n: usize) -> Result<Vec<String>, String> {
+    Ok(logs.backend.recent(n))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .plugin(instance::plugin())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
-        .manage(BackendPort(Mutex::new(0u16)))
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .manage(BackendPort::default())
+        .manage(SidecarState::default())
+        .manage(ApiToken::default())
+        .manage(LoopbackClient::default())
+        .manage(BackendStreams::default())
+        .manage(UiEvents::default())
+        .manage(notify::NotifyState::default())
+        .manage(capture::Capture::default())
+        .register_asynchronous_uri_scheme_protocol(proxy::SCHEME, |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(proxy::handle(&app, request).await);
+            });
+        })
         .setup(|app| {
             let handle = app.handle().clone();
 
-            // Dev mode: dev.sh sets PAGENODE_BACKEND_PORT — use it directly.
-            // Prod mode: spawn the PyInstaller sidecar, read PORT= from stdout.
+            app.manage(Logs::new(&paths::logs_dir(&handle)?));
+            app.manage(prefs::Prefs::load(
+                paths::pagenode_home(&handle)?.join(prefs::PREFS_FILENAME),
+            ));
+
+            menu::init(&handle)?;
+            tray::init(&handle)?;
+            notify::watch_documents(handle.clone());
+            window_state::restore(&handle)?;
+            capture::init(&handle);
+            windows::init(&handle);
+            import::init(&handle);
+            review_queue::init(&handle)?;
+
+            health::monitor(handle.clone());
+
+            // Files and pagenode:// links the OS launched us with.
+            import::import_args(&handle, std::env::args().skip(1));
+            deep_link::open_args(&handle, std::env::args().skip(1));
+
+            // External backend: dev.sh sets PAGENODE_BACKEND_PORT and
+            // PAGENODE_API_TOKEN — use them directly.
             if let Ok(port_str) = std::env::var("PAGENODE_BACKEND_PORT") {
                 if let Ok(port) = port_str.trim().parse::<u16>() {
-                    *handle.state::<BackendPort>().0.lock().unwrap() = port;
+                    let token = std::env::var(token::TOKEN_ENV).unwrap_or_default();
+                    handle.state::<ApiToken>().set(token);
+                    sidecar::set_state(&handle, BackendState::Ready { port });
                     return Ok(());
                 }
             }
 
-            // Production: spawn backend sidecar and wait for PORT= line on stdout.
-            tauri::async_runtime::spawn(async move {
-                let (mut rx, _child) = handle
-                    .shell()
-                    .sidecar("pagenode-backend")
-                    .expect("pagenode-backend sidecar binary not found")
-                    .spawn()
-                    .expect("failed to spawn pagenode-backend sidecar");
-
-                while let Some(event) = rx.recv().await {
-                    match event {
-                        CommandEvent::Stdout(line_bytes) => {
-                            let line = String::from_utf8_lossy(&line_bytes);
-                            if let Some(port_str) = line.trim().strip_prefix("PORT=") {
-                                if let Ok(port) = port_str.trim().parse::<u16>() {
-                                    *handle.state::<BackendPort>().0.lock().unwrap() = port;
-                                    break;
-                                }
-                            }
-                        }
-                        CommandEvent::Error(err) => {
-                            eprintln!("[pagenode] backend sidecar error: {err}");
-                            break;
-                        }
-                        _ => {}
-                    }
-                }
-            });
+            // Otherwise supervise the backend ourselves, restarting it if it
+            // dies: from backend/.venv in debug builds, the bundled sidecar
+            // in release.
+            sidecar::supervise(handle, sidecar::Launch::detect());
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![get_backend_port])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![
+            get_backend_port,
+            get_backend_state,
+            get_recent_logs,
+            diagnostics::export_diagnostics,
+            backup::create_backup,
+            backup::restore_backup,
+            proxy::stream_backend,
+            proxy::cancel_backend_stream,
+            prefs::get_shell_prefs,
+            prefs::set_shell_prefs,
+            capture::get_capture_text,
+            library::list_documents,
+            library::due_card_count,
+            library::get_settings,
+            window_state::set_last_route,
+            window_state::reset_window_state,
+            windows::open_window,
+            windows::focus_window,
+            windows::close_window,
+            scheduler::review_card,
+            scheduler::preview_intervals,
+            scheduler::get_scheduler,
+            scheduler::set_scheduler,
+            scheduler::optimize_fsrs,
+            review_queue::get_review_queue,
+            ui_events::ui_ready
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // Give the backend a chance to close its databases before we go.
+            RunEvent::ExitRequested { api, code, .. }
+                if app.state::<SidecarState>().begin_shutdown() =>
+            {
+                window_state::save(app);
+                api.prevent_exit();
+                let handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    sidecar::shutdown(&handle).await;
+                    handle.exit(code.unwrap_or(0));
+                });
+            }
+            // macOS delivers "Open with" and deep links as an event rather
+            // than argv.
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            RunEvent::Opened { urls } => {
+                let (files, links): (Vec<_>, Vec<_>) =
+                    urls.into_iter().partition(|url| url.scheme() == "file");
+                for link in links {
+                    deep_link::open(app, link.as_str());
+                }
+                let paths: Vec<_> = files
+                    .into_iter()
+                    .filter_map(|url| url.to_file_path().ok())
+                    .collect();
+                if !paths.is_empty() {
+                    instance::focus_main_window(app);
+                    import::import_paths(app, paths);
+                }
+            }
+            // Dock icon clicked while the window is hidden in the background.
+            #[cfg(target_os = "macos")]
+            RunEvent::Reopen { .. } => instance::focus_main_window(app),
+            _ => {}
+        });
 }
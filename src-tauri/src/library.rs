@@ -0,0 +1,144 @@
+//! Library views read straight from SQLite.
+//!
+//! The PyInstaller backend takes several seconds to import Python, ChromaDB
+//! and Kuzu before it answers anything. These commands read the same rows
+//! from `pagenode.db` through a read-only connection, in the shapes of the
+//! backend's `GET /documents/`, the `/quiz/due` filter and `GET /settings/`,
+//! so the bookshelf can render while the sidecar is still starting.
+
+use std::collections::BTreeMap;
+
+use rusqlite::{Connection, Row};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::db;
+
+const DEFAULT_LIMIT: u32 = 50;
+
+/// The backend's `Document` model.
+#[derive(Debug, Clone, Serialize)]
+pub struct Document {
+    pub id: String,
+    pub title: String,
+    pub author: String,
+    pub file_type: String,
+    pub file_path: String,
+    pub file_hash: String,
+    pub file_size: i64,
+    pub page_count: i64,
+    pub cover_color: String,
+    pub cover_texture: String,
+    pub ai_confidence: f64,
+    pub status: String,
+    pub concept_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentList {
+    pub items: Vec<Document>,
+    pub total: u64,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+impl Document {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        // The table's defaults stand in for NULLs, as the backend's model
+        // would reject them.
+        Ok(Self {
+            id: row.get("id")?,
+            title: row.get("title")?,
+            author: row.get::<_, Option<_>>("author")?.unwrap_or_default(),
+            file_type: row.get("file_type")?,
+            file_path: row.get("file_path")?,
+            file_hash: row.get("file_hash")?,
+            file_size: row.get("file_size")?,
+            page_count: row.get::<_, Option<_>>("page_count")?.unwrap_or(0),
+            cover_color: row
+                .get::<_, Option<_>>("cover_color")?
+                .unwrap_or_else(|| "charcoal".to_string()),
+            cover_texture: row
+                .get::<_, Option<_>>("cover_texture")?
+                .unwrap_or_else(|| "plain".to_string()),
+            ai_confidence: row.get::<_, Option<_>>("ai_confidence")?.unwrap_or(0.0),
+            status: row
+                .get::<_, Option<_>>("status")?
+                .unwrap_or_else(|| "pending".to_string()),
+            concept_count: row.get::<_, Option<_>>("concept_count")?.unwrap_or(0),
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+        })
+    }
+}
+
+/// Newest first, like `GET /documents/`.
+#[tauri::command]
+pub async fn list_documents(
+    app: AppHandle,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<DocumentList, String> {
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    read(&app, move |conn| {
+        // One read transaction, so the count matches the page.
+        let tx = conn.unchecked_transaction()?;
+        let total = tx.query_row("SELECT COUNT(*) FROM documents", [], |row| row.get(0))?;
+        let items = tx
+            .prepare("SELECT * FROM documents ORDER BY created_at DESC LIMIT ?1 OFFSET ?2")?
+            .query_map([limit, offset], Document::from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(DocumentList {
+            items,
+            total,
+            offset,
+            limit,
+        })
+    })
+    .await
+}
+
+/// Cards the quiz would serve today.
+#[tauri::command]
+pub async fn due_card_count(app: AppHandle) -> Result<u64, String> {
+    read(&app, count_due).await
+}
+
+/// Every `settings` row, like `GET /settings/`.
+#[tauri::command]
+pub async fn get_settings(app: AppHandle) -> Result<BTreeMap<String, String>, String> {
+    read(&app, |conn| {
+        conn.prepare("SELECT key, value FROM settings")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect()
+    })
+    .await
+}
+
+/// Same filter as the backend's `get_due_flashcards`.
+pub fn count_due(conn: &Connection) -> rusqlite::Result<u64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM flashcards
+         WHERE next_review <= date('now') OR next_review IS NULL",
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Run `query` on a fresh read-only connection off the async runtime.
+pub async fn read<T, F>(app: &AppHandle, query: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+{
+    let path = db::path(app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db::open_read_only(&path)?;
+        query(&conn).map_err(|err| err.to_string())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
@@ -0,0 +1,237 @@
+//! Size-rotated log files under `~/.pagenode/logs/`.
+//!
+//! The sidecar's stdout and stderr are drained for its whole life and
+//! appended to `backend.log`; the shell's own messages go to `shell.log`
+//! through [`shell`]. When a file passes [`MAX_FILE_BYTES`] it is
+//! shifted to `backend.log.1`, `.1` to `.2`, and so on, keeping
+//! [`KEEP_ROTATED`] old files.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const KEEP_ROTATED: usize = 4;
+/// Upper bound for `get_recent_logs` so the webview can't ask for gigabytes.
+pub const MAX_RECENT_LINES: usize = 5000;
+
+/// One rotating log file, e.g. `backend.log`.
+pub struct LogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    inner: Mutex<Option<Writer>>,
+}
+
+struct Writer {
+    file: File,
+    size: u64,
+}
+
+impl LogFile {
+    pub fn new(dir: &Path, name: &str) -> Self {
+        Self::with_max_bytes(dir, name, MAX_FILE_BYTES)
+    }
+
+    fn with_max_bytes(dir: &Path, name: &str, max_bytes: u64) -> Self {
+        Self {
+            path: dir.join(name),
+            max_bytes,
+            inner: Mutex::new(None),
+        }
+    }
+
+    /// Append `[stream] line` with a timestamp. Logging never fails the caller.
+    pub fn append(&self, stream: &str, line: &str) {
+        let entry = format!(
+            "{} [{stream}] {}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            line.trim_end()
+        );
+        let mut inner = self.inner.lock().unwrap();
+        if let Err(err) = self.write_entry(&mut inner, entry.as_bytes()) {
+            eprintln!("[pagenode] failed to write {}: {err}", self.path.display());
+            // Reopen on the next line rather than writing to a dead handle.
+            *inner = None;
+        }
+    }
+
+    fn write_entry(&self, inner: &mut Option<Writer>, entry: &[u8]) -> io::Result<()> {
+        if inner
+            .as_ref()
+            .is_some_and(|w| w.size + entry.len() as u64 > self.max_bytes)
+        {
+            *inner = None;
+            self.rotate()?;
+        }
+        let writer = match inner {
+            Some(writer) => writer,
+            None => inner.insert(self.open()?),
+        };
+        writer.file.write_all(entry)?;
+        writer.size += entry.len() as u64;
+        Ok(())
+    }
+
+    fn open(&self) -> io::Result<Writer> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let size = file.metadata()?.len();
+        Ok(Writer { file, size })
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self) -> io::Result<()> {
+        for index in (1..KEEP_ROTATED).rev() {
+            let from = self.rotated(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+
+    /// The current file followed by its rotations, newest first.
+    pub fn files(&self) -> Vec<PathBuf> {
+        std::iter::once(self.path.clone())
+            .chain((1..=KEEP_ROTATED).map(|i| self.rotated(i)))
+            .filter(|p| p.exists())
+            .collect()
+    }
+
+    /// The last `n` lines across the current and rotated files, oldest first.
+    pub fn recent(&self, n: usize) -> Vec<String> {
+        let n = n.min(MAX_RECENT_LINES);
+        let mut lines: VecDeque<String> = VecDeque::with_capacity(n);
+        for path in self.files() {
+            if lines.len() >= n {
+                break;
+            }
+            let Ok(file) = File::open(&path) else {
+                continue;
+            };
+            let mut tail: VecDeque<String> = VecDeque::with_capacity(n);
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                if tail.len() == n {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+            // Older file: its lines go in front of what we already have.
+            while lines.len() < n {
+                match tail.pop_back() {
+                    Some(line) => lines.push_front(line),
+                    None => break,
+                }
+            }
+        }
+        lines.into()
+    }
+}
+
+/// Log files owned by the shell, managed as Tauri state.
+pub struct Logs {
+    pub backend: LogFile,
+    pub shell: LogFile,
+}
+
+impl Logs {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            backend: LogFile::new(dir, "backend.log"),
+            shell: LogFile::new(dir, "shell.log"),
+        }
+    }
+}
+
+/// Print a shell message to stderr and append it to `shell.log`.
+pub fn shell(app: &AppHandle, message: &str) {
+    eprintln!("[pagenode] {message}");
+    if let Some(logs) = app.try_state::<Logs>() {
+        logs.shell.append("shell", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINES_PER_FILE: u64 = 3;
+
+    /// A log in a fresh directory that rotates after [`LINES_PER_FILE`]
+    /// lines of the same length as `line 0`.
+    fn small_log() -> (tempfile::TempDir, LogFile) {
+        let dir = tempfile::tempdir().unwrap();
+        let probe = LogFile::new(dir.path(), "probe.log");
+        probe.append("t", "line 0");
+        let entry_len = fs::metadata(dir.path().join("probe.log")).unwrap().len();
+        let log = LogFile::with_max_bytes(dir.path(), "test.log", entry_len * LINES_PER_FILE);
+        (dir, log)
+    }
+
+    fn write_lines(log: &LogFile, count: usize) {
+        for i in 0..count {
+            log.append("t", &format!("line {i}"));
+        }
+    }
+
+    /// The messages in `path`, without timestamps.
+    fn messages(path: &Path) -> Vec<String> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| line.split_once("[t] ").unwrap().1.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rotates_when_the_next_line_would_pass_the_cap() {
+        let (dir, log) = small_log();
+        write_lines(&log, 3);
+        assert_eq!(log.files().len(), 1);
+
+        write_lines(&log, 1);
+        let current = dir.path().join("test.log");
+        assert_eq!(log.files(), [current.clone(), log.rotated(1)]);
+        assert_eq!(messages(&log.rotated(1)), ["line 0", "line 1", "line 2"]);
+        assert_eq!(messages(&current), ["line 0"]);
+    }
+
+    #[test]
+    fn keeps_only_the_newest_rotations() {
+        let (_dir, log) = small_log();
+        // Enough for seven files' worth.
+        write_lines(&log, 7 * LINES_PER_FILE as usize);
+        assert_eq!(log.files().len(), 1 + KEEP_ROTATED);
+        assert!(!log.rotated(KEEP_ROTATED + 1).exists());
+    }
+
+    #[test]
+    fn recent_lines_span_rotated_files() {
+        let (_dir, log) = small_log();
+        for i in 0..5 {
+            log.append("t", &format!("line {i}"));
+        }
+        let recent: Vec<_> = log
+            .recent(4)
+            .iter()
+            .map(|line| line.split_once("[t] ").unwrap().1.to_string())
+            .collect();
+        assert_eq!(recent, ["line 1", "line 2", "line 3", "line 4"]);
+        assert_eq!(log.recent(100).len(), 5);
+        assert!(log.recent(0).is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+//! Native application menu.
+//!
+//! Navigation items go to the webview as [`NAVIGATE_EVENT`](ui_events::NAVIGATE_EVENT),
+//! actions that need page state (focusing the search box, flipping to the
+//! graph and back) as [`MENU_EVENT`]. Importing, the data folder and update
+//! checks are handled here. Items that need the backend are disabled until
+//! the sidecar reports ready.
+
+use serde::Serialize;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Listener, Manager, Wry};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_updater::UpdaterExt;
+
+use crate::sidecar::{BackendState, SidecarState, STATUS_EVENT};
+use crate::{import, instance, logs, paths, ui_events};
+
+/// Action the page has to carry out, e.g. `{ "action": "search" }`.
+pub const MENU_EVENT: &str = "menu-action";
+
+// Prefixed so they never collide with the tray's ids: both menus' events
+// reach every menu event handler.
+const IMPORT_ID: &str = "menu-import";
+const DATA_FOLDER_ID: &str = "menu-data-folder";
+const CLOSE_WINDOW_ID: &str = "menu-close-window";
+const PREFERENCES_ID: &str = "menu-preferences";
+const LIBRARY_ID: &str = "menu-library";
+const TOGGLE_GRAPH_ID: &str = "menu-toggle-graph";
+const SEARCH_ID: &str = "menu-search";
+const START_QUIZ_ID: &str = "menu-start-quiz";
+const UPDATES_ID: &str = "menu-check-updates";
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum MenuAction {
+    Search,
+    ToggleGraph,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct MenuActionPayload {
+    action: MenuAction,
+}
+
+/// Items that are only usable while the backend is up.
+pub struct AppMenu {
+    import: MenuItem<Wry>,
+    search: MenuItem<Wry>,
+    start_quiz: MenuItem<Wry>,
+}
+
+impl AppMenu {
+    fn set_backend_ready(&self, ready: bool) {
+        for item in [&self.import, &self.search, &self.start_quiz] {
+            let _ = item.set_enabled(ready);
+        }
+    }
+}
+
+/// Build the menu bar, install it app-wide and keep it in step with the
+/// backend.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let import = MenuItem::with_id(
+        app,
+        IMPORT_ID,
+        "Import Document…",
+        false,
+        Some("CmdOrCtrl+O"),
+    )?;
+    let search = MenuItem::with_id(
+        app,
+        SEARCH_ID,
+        "Search Concepts…",
+        false,
+        Some("CmdOrCtrl+F"),
+    )?;
+    let start_quiz = MenuItem::with_id(
+        app,
+        START_QUIZ_ID,
+        "Start Quiz",
+        false,
+        Some("CmdOrCtrl+Shift+R"),
+    )?;
+    let preferences = MenuItem::with_id(
+        app,
+        PREFERENCES_ID,
+        "Preferences…",
+        true,
+        Some("CmdOrCtrl+,"),
+    )?;
+    let updates = MenuItem::with_id(app, UPDATES_ID, "Check for Updates…", true, None::<&str>)?;
+    let data_folder =
+        MenuItem::with_id(app, DATA_FOLDER_ID, "Open Data Folder", true, None::<&str>)?;
+    let close_window = MenuItem::with_id(
+        app,
+        CLOSE_WINDOW_ID,
+        "Close Window",
+        true,
+        Some("CmdOrCtrl+W"),
+    )?;
+    let separator = || PredefinedMenuItem::separator(app);
+
+    // macOS keeps Preferences, updates and Quit under the application menu;
+    // elsewhere they live in File and Help.
+    #[cfg(target_os = "macos")]
+    let app_menu = Some(Submenu::with_items(
+        app,
+        "PageNode",
+        true,
+        &[
+            &PredefinedMenuItem::about(app, None, None)?,
+            &separator()?,
+            &preferences,
+            &updates,
+            &separator()?,
+            &PredefinedMenuItem::services(app, None)?,
+            &separator()?,
+            &PredefinedMenuItem::hide(app, None)?,
+            &PredefinedMenuItem::hide_others(app, None)?,
+            &PredefinedMenuItem::show_all(app, None)?,
+            &separator()?,
+            &PredefinedMenuItem::quit(app, None)?,
+        ],
+    )?);
+    #[cfg(not(target_os = "macos"))]
+    let app_menu: Option<Submenu<Wry>> = None;
+
+    let file = Submenu::with_items(
+        app,
+        "File",
+        true,
+        &[&import, &data_folder, &separator()?, &close_window],
+    )?;
+    #[cfg(not(target_os = "macos"))]
+    {
+        file.append_items(&[
+            &separator()?,
+            &preferences,
+            &separator()?,
+            &PredefinedMenuItem::quit(app, None)?,
+        ])?;
+    }
+
+    let edit = Submenu::with_items(
+        app,
+        "Edit",
+        true,
+        &[
+            &PredefinedMenuItem::undo(app, None)?,
+            &PredefinedMenuItem::redo(app, None)?,
+            &separator()?,
+            &PredefinedMenuItem::cut(app, None)?,
+            &PredefinedMenuItem::copy(app, None)?,
+            &PredefinedMenuItem::paste(app, None)?,
+            &PredefinedMenuItem::select_all(app, None)?,
+        ],
+    )?;
+
+    let view = Submenu::with_items(
+        app,
+        "View",
+        true,
+        &[
+            &MenuItem::with_id(app, LIBRARY_ID, "Library", true, Some("CmdOrCtrl+1"))?,
+            &MenuItem::with_id(
+                app,
+                TOGGLE_GRAPH_ID,
+                "Toggle Knowledge Graph",
+                true,
+                Some("CmdOrCtrl+G"),
+            )?,
+            &separator()?,
+            &search,
+        ],
+    )?;
+
+    let study = Submenu::with_items(app, "Study", true, &[&start_quiz])?;
+
+    let menu = Menu::new(app)?;
+    if let Some(app_menu) = &app_menu {
+        menu.append(app_menu)?;
+    }
+    menu.append_items(&[&file, &edit, &view, &study])?;
+    #[cfg(not(target_os = "macos"))]
+    menu.append(&Submenu::with_items(
+        app,
+        "Help",
+        true,
+        &[
+            &updates,
+            &separator()?,
+            &PredefinedMenuItem::about(app, None, None)?,
+        ],
+    )?)?;
+    app.set_menu(menu)?;
+    app.on_menu_event(on_menu_event);
+
+    let state = AppMenu {
+        import,
+        search,
+        start_quiz,
+    };
+    state.set_backend_ready(backend_ready(app));
+    app.manage(state);
+
+    let handle = app.clone();
+    app.listen(STATUS_EVENT, move |_| {
+        handle
+            .state::<AppMenu>()
+            .set_backend_ready(backend_ready(&handle));
+    });
+
+    Ok(())
+}
+
+fn backend_ready(app: &AppHandle) -> bool {
+    matches!(
+        app.state::<SidecarState>().current(),
+        BackendState::Ready { .. }
+    )
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        IMPORT_ID => import::pick_documents(app),
+        DATA_FOLDER_ID => open_data_folder(app),
+        CLOSE_WINDOW_ID => {
+            let focused = app
+                .webview_windows()
+                .into_values()
+                .find(|window| window.is_focused().unwrap_or(false));
+            if let Some(window) = focused {
+                let _ = window.close();
+            }
+        }
+        PREFERENCES_ID => show_route(app, "/settings"),
+        LIBRARY_ID => show_route(app, "/"),
+        START_QUIZ_ID => show_route(app, "/quiz?session=due"),
+        TOGGLE_GRAPH_ID => send_action(app, MenuAction::ToggleGraph),
+        SEARCH_ID => send_action(app, MenuAction::Search),
+        UPDATES_ID => check_for_updates(app.clone()),
+        _ => {}
+    }
+}
+
+fn show_route(app: &AppHandle, route: &str) {
+    instance::focus_main_window(app);
+    ui_events::navigate(app, route);
+}
+
+fn send_action(app: &AppHandle, action: MenuAction) {
+    instance::focus_main_window(app);
+    ui_events::emit(app, MENU_EVENT, MenuActionPayload { action });
+}
+
+fn open_data_folder(app: &AppHandle) {
+    let opened = paths::pagenode_home(app)
+        .map_err(|err| err.to_string())
+        .and_then(|home| {
+            app.opener()
+                .open_path(home.to_string_lossy(), None::<&str>)
+                .map_err(|err| err.to_string())
+        });
+    if let Err(err) = opened {
+        logs::shell(app, &format!("failed to open data folder: {err}"));
+    }
+}
+
+/// Ask the update endpoint, then offer to install and restart.
+fn check_for_updates(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let checked = match app.updater() {
+            Ok(updater) => updater.check().await,
+            Err(err) => Err(err),
+        };
+        let update = match checked {
+            Ok(Some(update)) => update,
+            Ok(None) => {
+                app.dialog()
+                    .message("You're running the latest version of PageNode.")
+                    .title("No updates available")
+                    .show(|_| {});
+                return;
+            }
+            Err(err) => {
+                logs::shell(&app, &format!("update check failed: {err}"));
+                app.dialog()
+                    .message(format!("Couldn't check for updates: {err}"))
+                    .title("Update check failed")
+                    .kind(MessageDialogKind::Error)
+                    .show(|_| {});
+                return;
+            }
+        };
+
+        let handle = app.clone();
+        app.dialog()
+            .message(format!(
+                "PageNode {} is available (you have {}). Install it and restart now?",
+                update.version, update.current_version
+            ))
+            .title("Update available")
+            .buttons(MessageDialogButtons::OkCancelCustom(
+                "Install and Restart".into(),
+                "Later".into(),
+            ))
+            .show(move |install| {
+                if !install {
+                    return;
+                }
+                tauri::async_runtime::spawn(async move {
+                    match update.download_and_install(|_, _| {}, || {}).await {
+                        Ok(()) => handle.restart(),
+                        Err(err) => logs::shell(&handle, &format!("update install failed: {err}")),
+                    }
+                });
+            });
+    });
+}
@@ -0,0 +1,295 @@
+//! Native notifications for things that finish while nobody is looking.
+//!
+//! The shell polls `/documents/` and raises a notification when a document
+//! reaches a terminal status (ready, needs OCR, error). The tray's due-count
+//! poll feeds [`due_cards`], which reminds at most once a day. Both respect the
+//! per-type opt-outs and quiet hours in [`NotificationPrefs`]; a due reminder
+//! that falls in quiet hours is held until they end, document notifications
+//! are dropped.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate};
+use serde::Deserialize;
+use tauri::http::Method;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::prefs::{NotificationPrefs, Prefs};
+use crate::{logs, proxy};
+
+/// How often documents are checked while one is being ingested.
+const BUSY_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Documents fetched per request; the watcher pages until it has them all.
+const PAGE_SIZE: usize = 100;
+
+/// Statuses after which the backend does no more work on a document.
+const TERMINAL_STATUSES: &[&str] = &["ready", "concepts_ready", "needs_ocr", "error"];
+
+/// Date of the last due-cards reminder, so it fires once a day.
+#[derive(Default)]
+pub struct NotifyState {
+    due_notified_on: Mutex<Option<NaiveDate>>,
+}
+
+#[derive(Deserialize)]
+struct DocumentList {
+    items: Vec<DocumentSummary>,
+    total: usize,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Deserialize)]
+struct DocumentSummary {
+    id: String,
+    title: String,
+    status: String,
+    #[serde(default)]
+    concept_count: i64,
+}
+
+enum Kind {
+    Documents,
+    DueCards,
+}
+
+/// Whether a `kind` notification may be shown right now.
+fn allowed(app: &AppHandle, kind: Kind) -> bool {
+    let NotificationPrefs {
+        documents,
+        due_cards,
+        quiet_hours,
+    } = app.state::<Prefs>().get().notifications;
+    let enabled = match kind {
+        Kind::Documents => documents,
+        Kind::DueCards => due_cards,
+    };
+    enabled && !quiet_hours.is_some_and(|quiet| quiet.contains(Local::now().time()))
+}
+
+/// The user is already looking at the app.
+fn main_window_focused(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .is_some_and(|window| window.is_focused().unwrap_or(false))
+}
+
+fn show(app: &AppHandle, title: &str, body: &str) {
+    if let Err(err) = app.notification().builder().title(title).body(body).show() {
+        logs::shell(app, &format!("failed to show notification: {err}"));
+    }
+}
+
+/// Called with every fresh due count; reminds once per day when non-zero.
+pub fn due_cards(app: &AppHandle, total: usize, label: &str) {
+    if total == 0 || !allowed(app, Kind::DueCards) {
+        return;
+    }
+    let today = Local::now().date_naive();
+    let state = app.state::<NotifyState>();
+    let mut notified_on = state.due_notified_on.lock().unwrap();
+    if *notified_on == Some(today) {
+        return;
+    }
+    *notified_on = Some(today);
+    show(app, label, "Open PageNode to start today's review.");
+}
+
+fn is_terminal(status: &str) -> bool {
+    TERMINAL_STATUSES.contains(&status)
+}
+
+fn document_message(doc: &DocumentSummary) -> (String, String) {
+    let title = format!("'{}'", doc.title);
+    match doc.status.as_str() {
+        "concepts_ready" if doc.concept_count > 0 => (
+            format!("{title} is ready — {} concepts", doc.concept_count),
+            "Explore it in the knowledge graph.".to_string(),
+        ),
+        "needs_ocr" => (
+            format!("{title} needs OCR"),
+            "It looks like a scanned PDF with no text layer.".to_string(),
+        ),
+        "error" => (
+            format!("{title} failed to import"),
+            "Check the library for details.".to_string(),
+        ),
+        _ => (
+            format!("{title} is ready"),
+            "It has been added to your library.".to_string(),
+        ),
+    }
+}
+
+/// Document statuses as of the previous poll, keyed by id.
+type Known = HashMap<String, String>;
+
+/// What one poll of the document list found.
+struct Poll<'a> {
+    /// Documents that reached a terminal status since the previous poll.
+    finished: Vec<&'a DocumentSummary>,
+    /// Some document is still being worked on.
+    busy: bool,
+    known: Known,
+}
+
+/// Compare `docs` with the statuses from the previous poll. On the first poll
+/// (`previous` is `None`) nothing counts as newly finished, so documents that
+/// were already done at launch don't all announce themselves.
+fn poll_documents<'a>(previous: Option<&Known>, docs: &'a [DocumentSummary]) -> Poll<'a> {
+    let mut poll = Poll {
+        finished: Vec::new(),
+        busy: false,
+        known: HashMap::with_capacity(docs.len()),
+    };
+    for doc in docs {
+        let finished = is_terminal(&doc.status);
+        poll.busy |= !finished;
+        if let Some(previous) = previous {
+            let was_finished = previous.get(&doc.id).is_some_and(|s| is_terminal(s));
+            if finished && !was_finished {
+                poll.finished.push(doc);
+            }
+        }
+        poll.known.insert(doc.id.clone(), doc.status.clone());
+    }
+    poll
+}
+
+/// Every document, newest first, one page at a time.
+async fn fetch_documents(app: &AppHandle) -> Result<Vec<DocumentSummary>, String> {
+    let mut docs = Vec::new();
+    loop {
+        let page = fetch_page(app, docs.len()).await?;
+        let done = page.items.is_empty() || docs.len() + page.items.len() >= page.total;
+        docs.extend(page.items);
+        if done {
+            return Ok(docs);
+        }
+    }
+}
+
+async fn fetch_page(app: &AppHandle, offset: usize) -> Result<DocumentList, String> {
+    let path = format!("/documents/?offset={offset}&limit={PAGE_SIZE}");
+    let response = proxy::backend_request(app, Method::GET, &path)
+        .await?
+        .send()
+        .await
+        .map_err(|err| format!("backend request failed: {err}"))?;
+    if !response.status().is_success() {
+        return Err(format!("/documents/ returned {}", response.status()));
+    }
+    let body = response
+        .bytes()
+        .await
+        .map_err(|err| format!("failed to read backend response: {err}"))?;
+    serde_json::from_slice::<DocumentList>(&body)
+        .map_err(|err| format!("unexpected /documents/ response: {err}"))
+}
+
+/// Watch document statuses for the life of the app.
+pub fn watch_documents(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // `None` until the first successful poll.
+        let mut known: Option<Known> = None;
+        loop {
+            let mut busy = false;
+            if let Ok(docs) = fetch_documents(&app).await {
+                let poll = poll_documents(known.as_ref(), &docs);
+                if !poll.finished.is_empty()
+                    && allowed(&app, Kind::Documents)
+                    && !main_window_focused(&app)
+                {
+                    for doc in poll.finished {
+                        let (title, body) = document_message(doc);
+                        show(&app, &title, &body);
+                    }
+                }
+                busy = poll.busy;
+                known = Some(poll.known);
+            }
+            let interval = if busy {
+                BUSY_POLL_INTERVAL
+            } else {
+                IDLE_POLL_INTERVAL
+            };
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, status: &str) -> DocumentSummary {
+        DocumentSummary {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: status.to_string(),
+            concept_count: 0,
+        }
+    }
+
+    fn finished_ids(poll: &Poll) -> Vec<String> {
+        poll.finished.iter().map(|doc| doc.id.clone()).collect()
+    }
+
+    #[test]
+    fn first_poll_announces_nothing() {
+        let docs = [doc("a", "ready"), doc("b", "processing")];
+        let poll = poll_documents(None, &docs);
+        assert!(poll.finished.is_empty());
+        assert!(poll.busy);
+        assert_eq!(poll.known.len(), 2);
+    }
+
+    #[test]
+    fn announces_each_document_once_when_it_finishes() {
+        let first = [doc("a", "processing"), doc("b", "ready")];
+        let known = poll_documents(None, &first).known;
+
+        let second = [doc("a", "needs_ocr"), doc("b", "ready")];
+        let poll = poll_documents(Some(&known), &second);
+        assert_eq!(finished_ids(&poll), ["a"]);
+        assert!(!poll.busy);
+
+        let poll = poll_documents(Some(&poll.known), &second);
+        assert!(poll.finished.is_empty());
+    }
+
+    #[test]
+    fn announces_a_document_that_finished_between_polls() {
+        let known = poll_documents(None, &[doc("a", "ready")]).known;
+        let docs = [doc("new", "error"), doc("a", "ready")];
+        let poll = poll_documents(Some(&known), &docs);
+        assert_eq!(finished_ids(&poll), ["new"]);
+    }
+
+    #[test]
+    fn moving_between_terminal_statuses_is_not_announced_again() {
+        let known = poll_documents(None, &[doc("a", "ready")]).known;
+        let docs = [doc("a", "concepts_ready")];
+        let poll = poll_documents(Some(&known), &docs);
+        assert!(poll.finished.is_empty());
+    }
+
+    #[test]
+    fn parses_a_page_with_its_total() {
+        let body = r#"{"items": [{"id": "a", "title": "A", "status": "ready"}],
+                       "total": 250, "offset": 0, "limit": 100}"#;
+        let page: DocumentList = serde_json::from_str(body).unwrap();
+        assert_eq!(page.total, 250);
+        assert_eq!(page.items, [doc_titled("a", "A", "ready")]);
+    }
+
+    fn doc_titled(id: &str, title: &str, status: &str) -> DocumentSummary {
+        DocumentSummary {
+            title: title.to_string(),
+            ..doc(id, status)
+        }
+    }
+}
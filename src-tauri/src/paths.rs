@@ -0,0 +1,22 @@
+//! Locations under `~/.pagenode/`, mirroring `backend/app/config.py`.
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Manager};
+
+pub const SQLITE_FILENAME: &str = "pagenode.db";
+
+/// `~/.pagenode`
+pub fn pagenode_home(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(app.path().home_dir()?.join(".pagenode"))
+}
+
+/// `~/.pagenode/data` — SQLite, ChromaDB, Kuzu and imported files.
+pub fn data_dir(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(pagenode_home(app)?.join("data"))
+}
+
+/// `~/.pagenode/logs` — shell and sidecar logs.
+pub fn logs_dir(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(pagenode_home(app)?.join("logs"))
+}
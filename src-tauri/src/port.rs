@@ -0,0 +1,119 @@
+//! The backend's loopback port, published once the sidecar handshake completes.
+//!
+//! Port `0` means "not known yet": either the sidecar is still starting or it
+//! is being restarted. Callers that need a real port await [`BackendPort::wait`]
+//! instead of reading a value that may still be zero.
+
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+pub struct BackendPort(watch::Sender<u16>);
+
+impl Default for BackendPort {
+    fn default() -> Self {
+        Self(watch::Sender::new(0))
+    }
+}
+
+impl BackendPort {
+    /// The current port, or `None` while the backend isn't ready.
+    pub fn get(&self) -> Option<u16> {
+        match *self.0.borrow() {
+            0 => None,
+            port => Some(port),
+        }
+    }
+
+    /// Publish a port (or `0` to mark the backend as unavailable).
+    pub fn set(&self, port: u16) {
+        self.0.send_replace(port);
+    }
+
+    /// Resolve as soon as a non-zero port is published, or fail after `timeout`.
+    pub async fn wait(&self, timeout: Duration) -> Result<u16, String> {
+        let mut rx = self.0.subscribe();
+        let ready = async { rx.wait_for(|port| *port != 0).await.map(|port| *port) };
+        match tokio::time::timeout(timeout, ready).await {
+            Ok(Ok(port)) => Ok(port),
+            Ok(Err(_)) => Err("backend port channel closed".to_string()),
+            Err(_) => Err(format!(
+                "backend not ready after {}s",
+                timeout.as_secs_f32()
+            )),
+        }
+    }
+}
+
+/// Extract the port from the sidecar's `PORT=<n>` handshake line.
+pub fn parse_port_line(line: &str) -> Option<u16> {
+    line.trim()
+        .strip_prefix("PORT=")?
+        .trim()
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn parses_handshake_line() {
+        assert_eq!(parse_port_line("PORT=51234\n"), Some(51234));
+        assert_eq!(parse_port_line("  PORT= 8000 "), Some(8000));
+        assert_eq!(parse_port_line("PORT=0"), None);
+        assert_eq!(parse_port_line("PORT=99999"), None);
+        assert_eq!(parse_port_line("INFO: Started server process"), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_resolves_on_late_port_line() {
+        let port = Arc::new(BackendPort::default());
+        assert_eq!(port.get(), None);
+
+        let writer = Arc::clone(&port);
+        tokio::spawn(async move {
+            // Log noise first, then the handshake a few seconds later.
+            for line in ["Loading models...", "", "PORT=43117"] {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                if let Some(p) = parse_port_line(line) {
+                    writer.set(p);
+                }
+            }
+        });
+
+        assert_eq!(port.wait(Duration::from_secs(30)).await, Ok(43117));
+        assert_eq!(port.get(), Some(43117));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_times_out_without_handshake() {
+        let port = BackendPort::default();
+        assert!(port.wait(Duration::from_secs(5)).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_skips_restart_gap() {
+        let port = Arc::new(BackendPort::default());
+        port.set(40000);
+        port.set(0);
+
+        let writer = Arc::clone(&port);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            writer.set(40001);
+        });
+
+        assert_eq!(port.wait(Duration::from_secs(10)).await, Ok(40001));
+    }
+
+    #[tokio::test]
+    async fn wait_returns_immediately_when_ready() {
+        let port = BackendPort::default();
+        port.set(5555);
+        assert_eq!(port.wait(Duration::from_millis(1)).await, Ok(5555));
+    }
+}
@@ -0,0 +1,244 @@
+//! Preferences that belong to the shell rather than the backend.
+//!
+//! They have to be readable before the sidecar is up (and after it is gone),
+//! so they live in `~/.pagenode/shell.json` instead of the SQLite `settings`
+//! table. Missing or unreadable files fall back to the defaults.
+//!
+//! The notification opt-outs and quiet hours are here too, although the
+//! notifications are about backend data. The backend never reads them, and the
+//! shell checks them on every document poll and due-count update. Keeping them
+//! beside the other shell prefs gives the settings page one command and one
+//! [`PREFS_EVENT`] for everything the shell owns.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::logs;
+
+pub const PREFS_FILENAME: &str = "shell.json";
+/// Emitted with the full [`ShellPrefs`] whenever they change.
+pub const PREFS_EVENT: &str = "shell-prefs";
+
+/// Global shortcut that opens the capture window.
+pub const DEFAULT_CAPTURE_SHORTCUT: &str = "CommandOrControl+Shift+Y";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShellPrefs {
+    /// Closing the main window hides it and leaves the tray and sidecar up.
+    pub keep_running_in_background: bool,
+    pub notifications: NotificationPrefs,
+    /// Accelerator such as `CommandOrControl+Shift+Y`; empty disables capture.
+    pub capture_shortcut: String,
+}
+
+impl Default for ShellPrefs {
+    fn default() -> Self {
+        Self {
+            keep_running_in_background: false,
+            notifications: NotificationPrefs::default(),
+            capture_shortcut: DEFAULT_CAPTURE_SHORTCUT.to_string(),
+        }
+    }
+}
+
+/// Which native notifications to raise, and when not to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationPrefs {
+    /// A document finished (or failed) ingestion.
+    pub documents: bool,
+    /// Daily "N cards due" reminder.
+    pub due_cards: bool,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl Default for NotificationPrefs {
+    fn default() -> Self {
+        Self {
+            documents: true,
+            due_cards: true,
+            quiet_hours: None,
+        }
+    }
+}
+
+/// Local `HH:MM` range, which may wrap past midnight (`22:00`–`08:00`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
+
+impl QuietHours {
+    fn parse(time: &str) -> Result<NaiveTime, String> {
+        NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| format!("invalid time: {time:?}"))
+    }
+
+    /// Whether `now` falls inside the range; an empty range never does.
+    pub fn contains(&self, now: NaiveTime) -> bool {
+        let (Ok(start), Ok(end)) = (Self::parse(&self.start), Self::parse(&self.end)) else {
+            return false;
+        };
+        if start <= end {
+            start <= now && now < end
+        } else {
+            now >= start || now < end
+        }
+    }
+}
+
+impl ShellPrefs {
+    fn validate(&self) -> Result<(), String> {
+        let shortcut = self.capture_shortcut.trim();
+        if !shortcut.is_empty() {
+            shortcut
+                .parse::<Shortcut>()
+                .map_err(|err| format!("invalid shortcut {shortcut:?}: {err}"))?;
+        }
+        if let Some(quiet) = &self.notifications.quiet_hours {
+            QuietHours::parse(&quiet.start)?;
+            QuietHours::parse(&quiet.end)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Prefs {
+    path: PathBuf,
+    inner: Mutex<ShellPrefs>,
+}
+
+impl Prefs {
+    pub fn load(path: PathBuf) -> Self {
+        let inner = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            inner: Mutex::new(inner),
+        }
+    }
+
+    pub fn get(&self) -> ShellPrefs {
+        self.inner.lock().unwrap().clone()
+    }
+
+    /// Apply `change` and write the result out, holding the lock throughout
+    /// so that concurrent updates (tray and settings page) don't drop one
+    /// another.
+    fn modify(&self, change: impl FnOnce(&mut ShellPrefs)) -> Result<ShellPrefs, String> {
+        let mut inner = self.inner.lock().unwrap();
+        let mut prefs = inner.clone();
+        change(&mut prefs);
+        prefs.validate()?;
+        write_atomic(&self.path, &prefs)
+            .map_err(|err| format!("failed to save {}: {err}", self.path.display()))?;
+        *inner = prefs.clone();
+        Ok(prefs)
+    }
+}
+
+fn write_atomic(path: &Path, prefs: &ShellPrefs) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(prefs)?)?;
+    fs::rename(tmp, path)
+}
+
+/// Apply `change`, persist the result and tell every listener.
+pub fn update(app: &AppHandle, change: impl FnOnce(&mut ShellPrefs)) -> Result<ShellPrefs, String> {
+    let prefs = app.state::<Prefs>().modify(change)?;
+    if let Err(err) = app.emit(PREFS_EVENT, &prefs) {
+        logs::shell(app, &format!("failed to emit {PREFS_EVENT}: {err}"));
+    }
+    Ok(prefs)
+}
+
+#[tauri::command]
+pub fn get_shell_prefs(prefs: State<Prefs>) -> ShellPrefs {
+    prefs.get()
+}
+
+#[tauri::command]
+pub fn set_shell_prefs(app: AppHandle, prefs: ShellPrefs) -> Result<ShellPrefs, String> {
+    update(&app, |current| *current = prefs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> NaiveTime {
+        QuietHours::parse(time).unwrap()
+    }
+
+    fn quiet(start: &str, end: &str) -> QuietHours {
+        QuietHours {
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn quiet_hours_contains() {
+        let cases = [
+            // Same-day window; the start minute is in, the end minute is out.
+            ("13:00", "14:00", "12:59", false),
+            ("13:00", "14:00", "13:00", true),
+            ("13:00", "14:00", "13:59", true),
+            ("13:00", "14:00", "14:00", false),
+            // Wrapping past midnight.
+            ("22:00", "07:00", "21:59", false),
+            ("22:00", "07:00", "22:00", true),
+            ("22:00", "07:00", "23:59", true),
+            ("22:00", "07:00", "00:00", true),
+            ("22:00", "07:00", "06:59", true),
+            ("22:00", "07:00", "07:00", false),
+            ("22:00", "07:00", "12:00", false),
+            // Equal ends are an empty window, not a whole day.
+            ("09:00", "09:00", "08:59", false),
+            ("09:00", "09:00", "09:00", false),
+            ("09:00", "09:00", "21:00", false),
+            // Running up to midnight and starting from it.
+            ("00:00", "06:00", "00:00", true),
+            ("18:00", "00:00", "23:59", true),
+            ("18:00", "00:00", "00:00", false),
+        ];
+        for (start, end, now, expected) in cases {
+            assert_eq!(
+                quiet(start, end).contains(at(now)),
+                expected,
+                "{start}-{end} at {now}"
+            );
+        }
+    }
+
+    #[test]
+    fn unparsable_quiet_hours_never_apply() {
+        assert!(!quiet("late", "07:00").contains(at("23:00")));
+        assert!(!quiet("22:00", "25:00").contains(at("23:00")));
+    }
+
+    #[test]
+    fn concurrent_updates_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefs = Prefs::load(dir.path().join(PREFS_FILENAME));
+        std::thread::scope(|scope| {
+            scope.spawn(|| prefs.modify(|p| p.keep_running_in_background = true));
+            scope.spawn(|| prefs.modify(|p| p.notifications.due_cards = false));
+        });
+        let saved = Prefs::load(dir.path().join(PREFS_FILENAME)).get();
+        assert!(saved.keep_running_in_background);
+        assert!(!saved.notifications.due_cards);
+    }
+}
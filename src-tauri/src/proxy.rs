@@ -0,0 +1,375 @@
+//! `pagenode-api://` — the webview's only route to the backend.
+//!
+//! Requests to `pagenode-api://localhost/<path>` (`http://pagenode-api.localhost`
+//! on Windows) are forwarded to the sidecar over loopback with the per-launch
+//! token attached, so the webview never needs the port or the secret and the
+//! CSP doesn't have to allow arbitrary localhost ports.
+//!
+//! The scheme can't stream in either direction: wry hands the handler the
+//! whole request body as a `Vec<u8>`, and `UriSchemeResponder::respond` takes
+//! one complete response. Ordinary responses are therefore buffered, and a
+//! `text/event-stream` response, which would never complete, is answered with
+//! a 502 naming the alternative: [`stream_backend`], which relays a GET's
+//! response to the webview over an IPC channel chunk by chunk as it arrives.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::http::{header, HeaderMap, Method, Request, Response, StatusCode};
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Notify;
+
+use crate::logs;
+use crate::token::{ApiToken, TOKEN_HEADER};
+use crate::{BackendPort, PORT_WAIT_TIMEOUT};
+
+pub const SCHEME: &str = "pagenode-api";
+
+/// Headers that describe the webview → shell hop and must not be forwarded.
+const HOP_BY_HOP: &[&str] = &[
+    "connection",
+    "host",
+    "keep-alive",
+    "origin",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Shared HTTP client so loopback connections are pooled across requests.
+/// It ignores `HTTP(S)_PROXY`, which would otherwise route 127.0.0.1 through
+/// the user's proxy.
+pub struct LoopbackClient(reqwest::Client);
+
+impl Default for LoopbackClient {
+    fn default() -> Self {
+        // The updater builds reqwest without a TLS provider and only installs
+        // one when it checks for updates; building a client before that panics.
+        if rustls::crypto::CryptoProvider::get_default().is_none() {
+            let _ = rustls::crypto::ring::default_provider().install_default();
+        }
+        Self(
+            reqwest::Client::builder()
+                .no_proxy()
+                .build()
+                .expect("TLS backend unavailable"),
+        )
+    }
+}
+
+impl LoopbackClient {
+    pub fn client(&self) -> &reqwest::Client {
+        &self.0
+    }
+}
+
+pub async fn handle(app: &AppHandle, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    if request.method() == Method::OPTIONS {
+        return with_cors(
+            Response::builder()
+                .status(StatusCode::NO_CONTENT)
+                .header(header::ACCESS_CONTROL_ALLOW_METHODS, "*")
+                .header(header::ACCESS_CONTROL_ALLOW_HEADERS, "*"),
+        )
+        .body(Vec::new())
+        .unwrap();
+    }
+
+    match forward(app, request).await {
+        Ok(response) => response,
+        Err(detail) => {
+            logs::shell(app, &format!("{SCHEME} proxy error: {detail}"));
+            let body = serde_json::json!({ "detail": detail }).to_string();
+            with_cors(Response::builder().status(StatusCode::SERVICE_UNAVAILABLE))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(body.into_bytes())
+                .unwrap()
+        }
+    }
+}
+
+/// Start a request to the backend on behalf of the shell itself, waiting for
+/// the sidecar to be ready and attaching the API token.
+pub async fn backend_request(
+    app: &AppHandle,
+    method: Method,
+    path: &str,
+) -> Result<reqwest::RequestBuilder, String> {
+    let port = app.state::<BackendPort>().wait(PORT_WAIT_TIMEOUT).await?;
+    let token = app.state::<ApiToken>().get();
+    Ok(app
+        .state::<LoopbackClient>()
+        .client()
+        .request(method, format!("http://127.0.0.1:{port}{path}"))
+        .header(TOKEN_HEADER, token))
+}
+
+async fn forward(app: &AppHandle, request: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, String> {
+    let (parts, body) = request.into_parts();
+    let path = parts
+        .uri
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+
+    let upstream = backend_request(app, parts.method, path)
+        .await?
+        .headers(strip_hop_by_hop(&parts.headers))
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| format!("backend request failed: {err}"))?;
+
+    if is_event_stream(upstream.headers()) {
+        // Dropping `upstream` closes the connection and ends the stream.
+        let body = serde_json::json!({
+            "detail": format!(
+                "{path} streams events, which {SCHEME}:// can't relay; use the stream_backend command"
+            )
+        });
+        return with_cors(Response::builder().status(StatusCode::BAD_GATEWAY))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body.to_string().into_bytes())
+            .map_err(|err| err.to_string());
+    }
+
+    let mut builder = with_cors(Response::builder().status(upstream.status()));
+    for (name, value) in strip_hop_by_hop(upstream.headers()).iter() {
+        builder = builder.header(name, value);
+    }
+    let bytes = upstream
+        .bytes()
+        .await
+        .map_err(|err| format!("failed to read backend response: {err}"))?;
+    builder.body(bytes.to_vec()).map_err(|err| err.to_string())
+}
+
+/// One message of a [`stream_backend`] response, in order: `head`, any
+/// number of `chunk`s, then `end`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum StreamEvent {
+    /// Sent as soon as the backend's headers arrive.
+    Head {
+        status: u16,
+        content_type: Option<String>,
+    },
+    Chunk {
+        data: Vec<u8>,
+    },
+    End,
+}
+
+/// Cancellers for the streams [`stream_backend`] is relaying, by channel id.
+#[derive(Default)]
+pub struct BackendStreams(Mutex<HashMap<u32, Arc<Notify>>>);
+
+/// GET `path` from the backend and relay the response over `on_event` as it
+/// arrives, for the server-sent events `pagenode-api://` can't carry.
+/// Resolves once the body ends or [`cancel_backend_stream`] is called.
+#[tauri::command]
+pub async fn stream_backend(
+    app: AppHandle,
+    streams: State<'_, BackendStreams>,
+    path: String,
+    on_event: Channel<StreamEvent>,
+) -> Result<(), String> {
+    let id = on_event.id();
+    let cancel = Arc::new(Notify::new());
+    streams.0.lock().unwrap().insert(id, cancel.clone());
+    let relayed = async {
+        let response = backend_request(&app, Method::GET, &path)
+            .await?
+            .send()
+            .await
+            .map_err(|err| format!("backend request failed: {err}"))?;
+        relay(response, &cancel, |event| {
+            on_event.send(event).map_err(|err| err.to_string())
+        })
+        .await
+    }
+    .await;
+    streams.0.lock().unwrap().remove(&id);
+    relayed
+}
+
+/// Stop relaying the stream whose channel has id `id`; the backend sees the
+/// connection close.
+#[tauri::command]
+pub fn cancel_backend_stream(streams: State<BackendStreams>, id: u32) {
+    if let Some(cancel) = streams.0.lock().unwrap().get(&id) {
+        cancel.notify_one();
+    }
+}
+
+/// Pass `response` to `emit` piece by piece, never holding more than the
+/// chunk in hand.
+async fn relay(
+    mut response: reqwest::Response,
+    cancel: &Notify,
+    mut emit: impl FnMut(StreamEvent) -> Result<(), String>,
+) -> Result<(), String> {
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    emit(StreamEvent::Head {
+        status: response.status().as_u16(),
+        content_type,
+    })?;
+    loop {
+        let chunk = tokio::select! {
+            _ = cancel.notified() => return Ok(()),
+            chunk = response.chunk() => {
+                chunk.map_err(|err| format!("failed to read backend response: {err}"))?
+            }
+        };
+        match chunk {
+            Some(data) => emit(StreamEvent::Chunk {
+                data: data.to_vec(),
+            })?,
+            None => return emit(StreamEvent::End),
+        }
+    }
+}
+
+fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim_start().starts_with("text/event-stream"))
+}
+
+fn strip_hop_by_hop(headers: &HeaderMap) -> HeaderMap {
+    let mut out = headers.clone();
+    for name in HOP_BY_HOP {
+        out.remove(*name);
+    }
+    out
+}
+
+/// The page's origin differs from the scheme's, so every answer needs CORS.
+fn with_cors(builder: tauri::http::response::Builder) -> tauri::http::response::Builder {
+    builder.header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    const EVENTS: [&str; 3] = [
+        "data: {\"status\": \"downloading\", \"percent\": 10.0}\n\n",
+        "data: {\"status\": \"downloading\", \"percent\": 55.0}\n\n",
+        "data: {\"status\": \"complete\", \"percent\": 100.0}\n\n",
+    ];
+
+    /// Serve one chunked `text/event-stream` response, writing each of
+    /// `events` only after `relayed` says the previous one came out the other
+    /// side, so a relay that buffers the body can never finish.
+    async fn serve_events(
+        listener: TcpListener,
+        events: &'static [&'static str],
+        mut relayed: mpsc::UnboundedReceiver<()>,
+    ) {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = [0u8; 4096];
+        let _ = socket.read(&mut request).await.unwrap();
+        socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                  transfer-encoding: chunked\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        for event in events {
+            let chunk = format!("{:x}\r\n{event}\r\n", event.len());
+            socket.write_all(chunk.as_bytes()).await.unwrap();
+            socket.flush().await.unwrap();
+            if relayed.recv().await.is_none() {
+                return;
+            }
+        }
+        socket.write_all(b"0\r\n\r\n").await.unwrap();
+    }
+
+    async fn get(addr: SocketAddr) -> reqwest::Response {
+        LoopbackClient::default()
+            .client()
+            .get(format!("http://{addr}/settings/models/download/progress"))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn relays_each_event_as_it_arrives() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (relayed_tx, relayed_rx) = mpsc::unbounded_channel();
+        let server = tokio::spawn(serve_events(listener, &EVENTS, relayed_rx));
+        let response = get(addr).await;
+
+        let cancel = Notify::new();
+        let mut events = Vec::new();
+        let relaying = relay(response, &cancel, |event| {
+            if matches!(event, StreamEvent::Chunk { .. }) {
+                let _ = relayed_tx.send(());
+            }
+            events.push(event);
+            Ok(())
+        });
+        tokio::time::timeout(Duration::from_secs(10), relaying)
+            .await
+            .expect("relay waited for the whole body")
+            .unwrap();
+        server.await.unwrap();
+
+        let mut expected = vec![StreamEvent::Head {
+            status: 200,
+            content_type: Some("text/event-stream".to_string()),
+        }];
+        expected.extend(EVENTS.iter().map(|event| StreamEvent::Chunk {
+            data: event.as_bytes().to_vec(),
+        }));
+        expected.push(StreamEvent::End);
+        assert_eq!(events, expected);
+    }
+
+    #[tokio::test]
+    async fn cancelling_stops_the_relay() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Never told to go on, so the server stalls after the first event.
+        let (_relayed_tx, relayed_rx) = mpsc::unbounded_channel();
+        let server = tokio::spawn(serve_events(listener, &EVENTS, relayed_rx));
+        let response = get(addr).await;
+
+        let cancel = Notify::new();
+        let mut events = Vec::new();
+        let relaying = relay(response, &cancel, |event| {
+            if matches!(event, StreamEvent::Chunk { .. }) {
+                cancel.notify_one();
+            }
+            events.push(event);
+            Ok(())
+        });
+        tokio::time::timeout(Duration::from_secs(10), relaying)
+            .await
+            .expect("cancel was ignored")
+            .unwrap();
+        server.abort();
+
+        assert_eq!(events.len(), 2);
+        assert!(!events.contains(&StreamEvent::End));
+    }
+}
@@ -0,0 +1,370 @@
+//! Reviews waiting to reach the backend.
+//!
+//! [`crate::scheduler`] reschedules a card without the sidecar, but concept
+//! mastery lives in Kuzu and only the backend can update it. Every graded
+//! card is therefore appended to `~/.pagenode/review-journal.jsonl` and
+//! replayed, oldest first, to `POST /quiz/{card_id}/mastery` whenever the
+//! backend is ready, so a crash mid-quiz loses nothing.
+//!
+//! The journal is append-only: a delivered review gets a `sent` line rather
+//! than being rewritten away, and the file is truncated once nothing is left
+//! pending. Each review carries a random id the backend remembers, so one
+//! that was delivered but never marked sent (the shell died in between) is
+//! not applied twice when it is replayed.
+
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::http::{header, Method, StatusCode};
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+use tokio::sync::Notify;
+
+use crate::scheduler::Grade;
+use crate::sidecar::{BackendState, SidecarState, STATUS_EVENT};
+use crate::{logs, paths, proxy};
+
+pub const JOURNAL_FILENAME: &str = "review-journal.jsonl";
+/// `{ pending }` — emitted whenever the number of undelivered reviews changes.
+pub const QUEUE_EVENT: &str = "review-queue";
+
+/// How often a non-empty queue is retried when nothing else wakes it.
+const RETRY_INTERVAL: Duration = Duration::from_secs(15);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedReview {
+    pub id: String,
+    pub card_id: String,
+    pub grade: Grade,
+    /// UTC, RFC 3339.
+    pub reviewed_at: String,
+}
+
+/// One line of the journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Entry {
+    Review(QueuedReview),
+    Sent { id: String },
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct QueueStatus {
+    pub pending: usize,
+}
+
+pub struct ReviewQueue {
+    path: PathBuf,
+    pending: Mutex<VecDeque<QueuedReview>>,
+    /// Poked when there may be something to deliver.
+    wake: Notify,
+}
+
+impl ReviewQueue {
+    /// Pick up whatever an earlier run left undelivered. A torn last line is
+    /// cut off the file, or the next append would be glued onto it and lost.
+    fn load(path: PathBuf) -> Result<Self, String> {
+        let contents = match fs::read(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(format!("cannot read {}: {err}", path.display())),
+        };
+        let complete = contents
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |newline| newline + 1);
+        if complete < contents.len() {
+            OpenOptions::new()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_len(complete as u64))
+                .map_err(|err| format!("cannot truncate {}: {err}", path.display()))?;
+        }
+        let pending = pending_reviews(&String::from_utf8_lossy(&contents[..complete]));
+        Ok(Self::new(path, pending))
+    }
+
+    fn new(path: PathBuf, pending: VecDeque<QueuedReview>) -> Self {
+        Self {
+            path,
+            pending: Mutex::new(pending),
+            wake: Notify::new(),
+        }
+    }
+
+    fn status(&self) -> QueueStatus {
+        QueueStatus {
+            pending: self.pending.lock().unwrap().len(),
+        }
+    }
+
+    fn front(&self) -> Option<QueuedReview> {
+        self.pending.lock().unwrap().front().cloned()
+    }
+
+    fn push(&self, review: QueuedReview) -> Result<(), String> {
+        let mut pending = self.pending.lock().unwrap();
+        self.append(&Entry::Review(review.clone()))?;
+        pending.push_back(review);
+        Ok(())
+    }
+
+    /// Drop `id` from the front of the queue and record that it went out.
+    fn mark_sent(&self, id: &str) -> Result<(), String> {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.front().is_some_and(|review| review.id == id) {
+            pending.pop_front();
+        }
+        if pending.is_empty() {
+            File::create(&self.path)
+                .map(|_| ())
+                .map_err(|err| format!("cannot truncate {}: {err}", self.path.display()))
+        } else {
+            self.append(&Entry::Sent { id: id.to_string() })
+        }
+    }
+
+    fn append(&self, entry: &Entry) -> Result<(), String> {
+        let mut line = serde_json::to_string(entry).map_err(|err| err.to_string())?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| format!("cannot open {}: {err}", self.path.display()))?;
+        file.write_all(line.as_bytes())
+            .and_then(|()| file.sync_data())
+            .map_err(|err| format!("cannot write {}: {err}", self.path.display()))
+    }
+}
+
+/// Reviews in `journal` that were never marked sent, oldest first. A line
+/// that doesn't parse (the shell died mid-write) is skipped.
+fn pending_reviews(journal: &str) -> VecDeque<QueuedReview> {
+    let entries: Vec<Entry> = journal
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let sent: HashSet<&str> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Sent { id } => Some(id.as_str()),
+            Entry::Review(_) => None,
+        })
+        .collect();
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Review(review) if !sent.contains(review.id.as_str()) => Some(review.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Load the journal and start delivering it.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let path = paths::pagenode_home(app)?.join(JOURNAL_FILENAME);
+    let queue = ReviewQueue::load(path.clone()).unwrap_or_else(|err| {
+        // Left on disk for the next launch to retry.
+        logs::shell(app, &format!("review journal not loaded: {err}"));
+        ReviewQueue::new(path, VecDeque::new())
+    });
+    app.manage(queue);
+
+    let handle = app.clone();
+    app.listen(STATUS_EVENT, move |_| {
+        if backend_ready(&handle) {
+            handle.state::<ReviewQueue>().wake.notify_one();
+        }
+    });
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let queue = handle.state::<ReviewQueue>();
+        loop {
+            tokio::select! {
+                _ = queue.wake.notified() => {}
+                _ = tokio::time::sleep(RETRY_INTERVAL) => {}
+            }
+            deliver(&handle, &queue).await;
+        }
+    });
+    Ok(())
+}
+
+/// Journal a graded card for the backend. Never fails the review itself: a
+/// journal that can't be written only costs the mastery update.
+pub fn enqueue(app: &AppHandle, card_id: &str, grade: Grade) {
+    let queue = app.state::<ReviewQueue>();
+    let review = QueuedReview {
+        id: match review_id() {
+            Ok(id) => id,
+            Err(err) => {
+                logs::shell(app, &format!("review for {card_id} not journaled: {err}"));
+                return;
+            }
+        },
+        card_id: card_id.to_string(),
+        grade,
+        reviewed_at: Utc::now().to_rfc3339(),
+    };
+    if let Err(err) = queue.push(review) {
+        logs::shell(app, &format!("review for {card_id} not journaled: {err}"));
+        return;
+    }
+    emit_status(app, &queue);
+    queue.wake.notify_one();
+}
+
+#[tauri::command]
+pub fn get_review_queue(queue: State<ReviewQueue>) -> QueueStatus {
+    queue.status()
+}
+
+/// Send queued reviews in order until the queue is empty or one doesn't go
+/// through, which is retried later.
+async fn deliver(app: &AppHandle, queue: &ReviewQueue) {
+    while let Some(review) = queue.front() {
+        if !backend_ready(app) {
+            return;
+        }
+        match send(app, &review).await {
+            Ok(()) => {}
+            // The backend will never take it (the card was deleted, say);
+            // retrying would hold up everything behind it.
+            Err(Undeliverable::Rejected(detail)) => logs::shell(
+                app,
+                &format!("dropping queued review {}: {detail}", review.id),
+            ),
+            Err(Undeliverable::Unavailable(detail)) => {
+                logs::shell(
+                    app,
+                    &format!("queued review {} not delivered yet: {detail}", review.id),
+                );
+                return;
+            }
+        }
+        if let Err(err) = queue.mark_sent(&review.id) {
+            logs::shell(app, &format!("review journal: {err}"));
+        }
+        emit_status(app, queue);
+    }
+}
+
+enum Undeliverable {
+    Rejected(String),
+    Unavailable(String),
+}
+
+async fn send(app: &AppHandle, review: &QueuedReview) -> Result<(), Undeliverable> {
+    let path = format!("/quiz/{}/mastery", review.card_id);
+    let body = json!({ "grade": review.grade, "event_id": review.id });
+    let response = proxy::backend_request(app, Method::POST, &path)
+        .await
+        .map_err(Undeliverable::Unavailable)?
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|err| Undeliverable::Unavailable(err.to_string()))?;
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        status @ (StatusCode::NOT_FOUND | StatusCode::UNPROCESSABLE_ENTITY) => Err(
+            Undeliverable::Rejected(format!("backend returned {status}")),
+        ),
+        status => Err(Undeliverable::Unavailable(format!(
+            "backend returned {status}"
+        ))),
+    }
+}
+
+fn backend_ready(app: &AppHandle) -> bool {
+    matches!(
+        app.state::<SidecarState>().current(),
+        BackendState::Ready { .. }
+    )
+}
+
+fn emit_status(app: &AppHandle, queue: &ReviewQueue) {
+    if let Err(err) = app.emit(QUEUE_EVENT, queue.status()) {
+        logs::shell(app, &format!("failed to emit {QUEUE_EVENT}: {err}"));
+    }
+}
+
+fn review_id() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|err| format!("no OS randomness: {err}"))?;
+    Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(id: &str) -> QueuedReview {
+        QueuedReview {
+            id: id.to_string(),
+            card_id: format!("card-{id}"),
+            grade: Grade::Good,
+            reviewed_at: "2026-10-17T09:00:00+00:00".to_string(),
+        }
+    }
+
+    fn line(entry: Entry) -> String {
+        serde_json::to_string(&entry).unwrap() + "\n"
+    }
+
+    #[test]
+    fn sent_reviews_are_not_replayed() {
+        let journal = [
+            line(Entry::Review(review("a"))),
+            line(Entry::Review(review("b"))),
+            line(Entry::Sent { id: "a".into() }),
+            line(Entry::Review(review("c"))),
+        ]
+        .concat();
+        let pending: Vec<_> = pending_reviews(&journal)
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(pending, ["b", "c"]);
+    }
+
+    #[test]
+    fn torn_last_line_is_skipped() {
+        let mut journal = line(Entry::Review(review("a")));
+        journal.push_str(r#"{"kind":"review","id":"b","card_"#);
+        assert_eq!(pending_reviews(&journal), [review("a")]);
+    }
+
+    #[test]
+    fn review_after_a_torn_line_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(JOURNAL_FILENAME);
+        let mut journal = line(Entry::Review(review("a")));
+        journal.push_str(r#"{"kind":"review","id":"b","card_"#);
+        fs::write(&path, journal).unwrap();
+
+        let queue = ReviewQueue::load(path.clone()).unwrap();
+        queue.push(review("c")).unwrap();
+
+        let reloaded = ReviewQueue::load(path).unwrap();
+        let pending: Vec<_> = reloaded.pending.into_inner().unwrap().into();
+        assert_eq!(pending, [review("a"), review("c")]);
+    }
+
+    #[test]
+    fn missing_journal_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = ReviewQueue::load(dir.path().join(JOURNAL_FILENAME)).unwrap();
+        assert_eq!(queue.status().pending, 0);
+    }
+}
@@ -0,0 +1,266 @@
+//! FSRS-4.5: a card's memory is its stability (days until recall drops to
+//! 90%) and difficulty (1–10), and the next review is due when predicted
+//! retrievability falls to the desired retention.
+
+use serde::{Deserialize, Serialize};
+
+use super::sm2::MAX_INTERVAL_DAYS;
+use super::Grade;
+
+pub const PARAMETER_COUNT: usize = 17;
+
+/// The published FSRS-4.5 defaults, used until the user's own history has
+/// been optimized.
+pub const DEFAULT_PARAMETERS: [f64; PARAMETER_COUNT] = [
+    0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461, 2.1072,
+    0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+];
+
+/// Allowed range of each parameter, as enforced by the reference optimizer.
+pub const PARAMETER_BOUNDS: [(f64, f64); PARAMETER_COUNT] = [
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (1.0, 10.0),
+    (0.001, 4.0),
+    (0.001, 4.0),
+    (0.001, 0.75),
+    (0.0, 4.5),
+    (0.0, 0.8),
+    (0.001, 3.5),
+    (0.001, 5.0),
+    (0.001, 0.25),
+    (0.001, 0.9),
+    (0.0, 4.0),
+    (0.0, 1.0),
+    (1.0, 6.0),
+];
+
+pub const DESIRED_RETENTION: f64 = 0.9;
+pub const MIN_STABILITY: f64 = 0.01;
+pub const MIN_DIFFICULTY: f64 = 1.0;
+pub const MAX_DIFFICULTY: f64 = 10.0;
+
+const DECAY: f64 = -0.5;
+/// Chosen so that retrievability is exactly 90% after `stability` days.
+const FACTOR: f64 = 19.0 / 81.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Parameters(pub [f64; PARAMETER_COUNT]);
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self(DEFAULT_PARAMETERS)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryState {
+    pub stability: f64,
+    pub difficulty: f64,
+}
+
+/// FSRS rating: Again = 1 … Easy = 4.
+fn rating(grade: Grade) -> f64 {
+    f64::from(u8::from(grade) + 1)
+}
+
+/// Probability of recall `elapsed_days` after a review.
+pub fn retrievability(elapsed_days: f64, stability: f64) -> f64 {
+    (1.0 + FACTOR * elapsed_days / stability).powf(DECAY)
+}
+
+/// Whole days until retrievability falls to `retention`.
+pub fn next_interval(stability: f64, retention: f64) -> u32 {
+    let days = stability / FACTOR * (retention.powf(1.0 / DECAY) - 1.0);
+    days.round().clamp(1.0, f64::from(MAX_INTERVAL_DAYS)) as u32
+}
+
+impl Parameters {
+    /// Within bounds, and initial stability never lower for a better grade.
+    pub fn is_valid(&self) -> bool {
+        let w = &self.0;
+        w.iter()
+            .zip(PARAMETER_BOUNDS)
+            .all(|(v, (lo, hi))| v.is_finite() && (lo..=hi).contains(v))
+            && w[..4].windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    /// Memory after a card's first review.
+    pub fn init(&self, grade: Grade) -> MemoryState {
+        let w = &self.0;
+        let g = rating(grade);
+        MemoryState {
+            stability: w[g as usize - 1].max(MIN_STABILITY),
+            difficulty: self.init_difficulty(g),
+        }
+    }
+
+    /// Memory after reviewing a card `elapsed_days` after its last review.
+    pub fn review(&self, state: MemoryState, grade: Grade, elapsed_days: f64) -> MemoryState {
+        let w = &self.0;
+        let g = rating(grade);
+        let MemoryState {
+            stability: s,
+            difficulty: d,
+        } = state;
+        let r = retrievability(elapsed_days.max(0.0), s);
+
+        let stability = if grade == Grade::Again {
+            let forget =
+                w[11] * d.powf(-w[12]) * ((s + 1.0).powf(w[13]) - 1.0) * (w[14] * (1.0 - r)).exp();
+            forget.min(s)
+        } else {
+            let modifier = match grade {
+                Grade::Hard => w[15],
+                Grade::Easy => w[16],
+                _ => 1.0,
+            };
+            s * (w[8].exp()
+                * (11.0 - d)
+                * s.powf(-w[9])
+                * ((w[10] * (1.0 - r)).exp() - 1.0)
+                * modifier
+                + 1.0)
+        };
+
+        // Move with the grade, then drift back toward a "Good" first review.
+        let moved = d - w[6] * (g - 3.0);
+        let difficulty = w[7] * self.init_difficulty(3.0) + (1.0 - w[7]) * moved;
+
+        MemoryState {
+            stability: stability.max(MIN_STABILITY),
+            difficulty: difficulty.clamp(MIN_DIFFICULTY, MAX_DIFFICULTY),
+        }
+    }
+
+    fn init_difficulty(&self, rating: f64) -> f64 {
+        let w = &self.0;
+        (w[4] - (rating - 3.0) * w[5]).clamp(MIN_DIFFICULTY, MAX_DIFFICULTY)
+    }
+}
+
+/// Starting FSRS memory for a card only SM-2 has seen. SM-2 schedules the
+/// next review for when it still expects recall, so its interval stands in
+/// for stability; its 0.1–0.9 difficulty is stretched onto 1–10. Migration 4
+/// in `backend/app/db/sqlite.py` does the same in SQL.
+pub fn from_sm2(difficulty: f64, interval: u32) -> MemoryState {
+    MemoryState {
+        stability: f64::from(interval.max(1)),
+        difficulty: (1.0 + (difficulty - 0.1) * 11.25).clamp(MIN_DIFFICULTY, MAX_DIFFICULTY),
+    }
+}
+
+/// Inverse of the difficulty mapping in [`from_sm2`], so SM-2 picks up
+/// sensibly if the user switches back.
+pub fn to_sm2_difficulty(difficulty: f64) -> f64 {
+    0.1 + (difficulty - 1.0) / 11.25
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(Parameters::default().is_valid());
+    }
+
+    #[test]
+    fn interval_equals_stability_at_ninety_percent() {
+        assert!((retrievability(10.0, 10.0) - 0.9).abs() < 1e-12);
+        assert_eq!(next_interval(10.0, DESIRED_RETENTION), 10);
+    }
+
+    #[test]
+    fn first_review_uses_initial_stability() {
+        let params = Parameters::default();
+        assert_eq!(params.init(Grade::Good).stability, DEFAULT_PARAMETERS[2]);
+        assert_eq!(params.init(Grade::Good).difficulty, DEFAULT_PARAMETERS[4]);
+    }
+
+    #[test]
+    fn sm2_mapping_round_trips() {
+        for difficulty in [0.1, 0.3, 0.5, 0.9] {
+            let state = from_sm2(difficulty, 6);
+            assert!((to_sm2_difficulty(state.difficulty) - difficulty).abs() < 1e-9);
+            assert_eq!(state.stability, 6.0);
+        }
+    }
+
+    fn any_grade() -> impl Strategy<Value = Grade> {
+        prop::sample::select(Grade::ALL.to_vec())
+    }
+
+    fn any_state() -> impl Strategy<Value = MemoryState> {
+        (MIN_STABILITY..=36_500.0, MIN_DIFFICULTY..=MAX_DIFFICULTY).prop_map(
+            |(stability, difficulty)| MemoryState {
+                stability,
+                difficulty,
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn retrievability_decays_from_one(
+            stability in MIN_STABILITY..=36_500.0,
+            t in 0.0..=36_500.0f64,
+        ) {
+            let r = retrievability(t, stability);
+            prop_assert!(r > 0.0 && r <= 1.0);
+            prop_assert!(retrievability(t + 1.0, stability) < r);
+            prop_assert_eq!(retrievability(0.0, stability), 1.0);
+        }
+
+        #[test]
+        fn review_stays_in_bounds(
+            state in any_state(),
+            grade in any_grade(),
+            t in 0.0..=36_500.0f64,
+        ) {
+            let next = Parameters::default().review(state, grade, t);
+            prop_assert!(next.stability >= MIN_STABILITY && next.stability.is_finite());
+            prop_assert!((MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&next.difficulty));
+            let days = next_interval(next.stability, DESIRED_RETENTION);
+            prop_assert!((1..=MAX_INTERVAL_DAYS).contains(&days));
+        }
+
+        #[test]
+        fn recall_strengthens_and_lapse_weakens(
+            state in any_state(),
+            grade in any_grade(),
+            t in 0.0..=36_500.0f64,
+        ) {
+            let next = Parameters::default().review(state, grade, t);
+            if grade == Grade::Again {
+                prop_assert!(next.stability <= state.stability);
+            } else {
+                prop_assert!(next.stability >= state.stability);
+            }
+        }
+
+        #[test]
+        fn better_grades_are_never_due_sooner(state in any_state(), t in 0.0..=36_500.0f64) {
+            let params = Parameters::default();
+            let outcomes: Vec<_> =
+                Grade::ALL.iter().map(|&g| params.review(state, g, t)).collect();
+            for pair in outcomes.windows(2) {
+                prop_assert!(pair[0].stability <= pair[1].stability);
+                prop_assert!(pair[0].difficulty >= pair[1].difficulty);
+            }
+        }
+
+        #[test]
+        fn sm2_cards_map_into_range(
+            difficulty in 0.1..=0.9f64,
+            interval in 1..=MAX_INTERVAL_DAYS,
+        ) {
+            let state = from_sm2(difficulty, interval);
+            prop_assert!((MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&state.difficulty));
+            prop_assert_eq!(next_interval(state.stability, DESIRED_RETENTION), interval);
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! Spaced-repetition scheduling in the shell.
+//!
+//! The backend used to schedule every review, so grading a card failed
+//! whenever the sidecar was down or restarting. [`review_card`] now updates
+//! the card's row in `flashcards` directly and queues the grade in
+//! [`crate::review_queue`] for the backend, which owns concept mastery.
+//!
+//! Two schedulers are available, picked by the `scheduler` setting: SM-2 as
+//! the backend always ran it, and FSRS. Every review advances both models'
+//! state so switching between them carries on where the card stands, and is
+//! written to `review_log`, the history FSRS parameters are fitted to.
+
+mod fsrs;
+mod optimizer;
+mod sm2;
+
+use chrono::{Days, Local, NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{db, review_queue};
+use fsrs::{MemoryState, Parameters};
+use optimizer::{Fit, Review};
+use sm2::Sm2State;
+
+/// `settings` key holding the active [`Scheduler`].
+const SCHEDULER_KEY: &str = "scheduler";
+/// `settings` key holding fitted FSRS parameters as a JSON array.
+const FSRS_PARAMETERS_KEY: &str = "fsrs_parameters";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheduler {
+    #[default]
+    Sm2,
+    Fsrs,
+}
+
+impl Scheduler {
+    fn as_str(self) -> &'static str {
+        match self {
+            Scheduler::Sm2 => "sm2",
+            Scheduler::Fsrs => "fsrs",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "sm2" => Some(Scheduler::Sm2),
+            "fsrs" => Some(Scheduler::Fsrs),
+            _ => None,
+        }
+    }
+}
+
+/// Quiz button pressed, sent by the frontend as 0–3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Grade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Grade {
+    pub const ALL: [Grade; 4] = [Grade::Again, Grade::Hard, Grade::Good, Grade::Easy];
+
+    /// SM-2 quality score (0–5), the backend's `_GRADE_QUALITY`.
+    fn quality(self) -> u8 {
+        match self {
+            Grade::Again => 0,
+            Grade::Hard => 2,
+            Grade::Good => 4,
+            Grade::Easy => 5,
+        }
+    }
+}
+
+impl TryFrom<u8> for Grade {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Grade::ALL
+            .get(usize::from(value))
+            .copied()
+            .ok_or_else(|| "grade must be 0, 1, 2, or 3".to_string())
+    }
+}
+
+impl From<Grade> for u8 {
+    fn from(grade: Grade) -> Self {
+        grade as u8
+    }
+}
+
+/// Same shape as the backend's `ReviewResult`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewResult {
+    pub id: String,
+    pub interval: u32,
+    pub next_review: String,
+    pub repetitions: u32,
+    pub difficulty: f64,
+}
+
+/// Days until the card comes back for each grade, for the quiz buttons.
+#[derive(Debug, Clone, Serialize)]
+pub struct IntervalPreview {
+    pub again: u32,
+    pub hard: u32,
+    pub good: u32,
+    pub easy: u32,
+}
+
+/// What the settings page shows about scheduling.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchedulerSettings {
+    pub scheduler: Scheduler,
+    /// FSRS runs on parameters fitted to this user rather than the defaults.
+    pub optimized: bool,
+    pub logged_reviews: u64,
+}
+
+/// How cards are scheduled right now.
+struct Context {
+    scheduler: Scheduler,
+    parameters: Parameters,
+    today: NaiveDate,
+}
+
+impl Context {
+    fn load(conn: &Connection) -> Result<Self, String> {
+        let scheduler = get_setting(conn, SCHEDULER_KEY)?
+            .and_then(|value| Scheduler::parse(&value))
+            .unwrap_or_default();
+        let parameters = get_setting(conn, FSRS_PARAMETERS_KEY)?
+            .and_then(|value| serde_json::from_str::<Parameters>(&value).ok())
+            .filter(Parameters::is_valid)
+            .unwrap_or_default();
+        Ok(Self {
+            scheduler,
+            parameters,
+            today: Local::now().date_naive(),
+        })
+    }
+}
+
+/// A card's scheduling columns in `flashcards`.
+struct Card {
+    sm2: Sm2State,
+    /// `None` for a card that has never been reviewed.
+    memory: Option<MemoryState>,
+    last_review: Option<NaiveDate>,
+}
+
+/// Everything one grade does to a card.
+struct Outcome {
+    sm2: Sm2State,
+    memory: MemoryState,
+    interval: u32,
+}
+
+impl Card {
+    fn grade(&self, ctx: &Context, grade: Grade) -> Outcome {
+        let sm2 = self.sm2.review(grade);
+        let memory = match self.memory {
+            Some(memory) => {
+                let elapsed = self
+                    .last_review
+                    .map(|day| (ctx.today - day).num_days().max(0) as f64)
+                    .unwrap_or(memory.stability);
+                ctx.parameters.review(memory, grade, elapsed)
+            }
+            None => ctx.parameters.init(grade),
+        };
+        match ctx.scheduler {
+            Scheduler::Sm2 => Outcome {
+                interval: sm2.interval,
+                sm2,
+                memory,
+            },
+            Scheduler::Fsrs => {
+                let interval = fsrs::next_interval(memory.stability, fsrs::DESIRED_RETENTION);
+                // SM-2 takes over from FSRS's view of the card if the user
+                // switches back.
+                let difficulty = fsrs::to_sm2_difficulty(memory.difficulty)
+                    .clamp(sm2::MIN_DIFFICULTY, sm2::MAX_DIFFICULTY);
+                Outcome {
+                    sm2: Sm2State {
+                        difficulty,
+                        interval,
+                        repetitions: sm2.repetitions,
+                    },
+                    memory,
+                    interval,
+                }
+            }
+        }
+    }
+}
+
+/// Grade a card and store its new schedule.
+#[tauri::command]
+pub async fn review_card(
+    app: AppHandle,
+    card_id: String,
+    grade: Grade,
+) -> Result<ReviewResult, String> {
+    let path = db::path(&app)?;
+    let result = tauri::async_runtime::spawn_blocking(move || -> Result<ReviewResult, String> {
+        let mut conn = db::open_read_write(&path)?;
+        // Immediate, so the backend can't change the row between our read
+        // and write.
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(|err| err.to_string())?;
+
+        let ctx = Context::load(&tx)?;
+        let outcome = load_card(&tx, &card_id)?.grade(&ctx, grade);
+        let next_review = sm2::next_review(ctx.today, outcome.interval).to_string();
+        let today = ctx.today.to_string();
+        tx.execute(
+            "UPDATE flashcards
+             SET repetitions = ?1, interval = ?2, difficulty = ?3, next_review = ?4,
+                 stability = ?5, fsrs_difficulty = ?6, last_review = ?7, updated_at = ?8
+             WHERE id = ?9",
+            params![
+                outcome.sm2.repetitions,
+                outcome.interval,
+                outcome.sm2.difficulty,
+                next_review,
+                outcome.memory.stability,
+                outcome.memory.difficulty,
+                today,
+                Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                card_id,
+            ],
+        )
+        .map_err(|err| err.to_string())?;
+        tx.execute(
+            "INSERT INTO review_log (card_id, grade, reviewed_on, scheduler)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![card_id, u8::from(grade), today, ctx.scheduler.as_str()],
+        )
+        .map_err(|err| err.to_string())?;
+        tx.commit().map_err(|err| err.to_string())?;
+
+        Ok(ReviewResult {
+            id: card_id,
+            interval: outcome.interval,
+            next_review,
+            repetitions: outcome.sm2.repetitions,
+            difficulty: outcome.sm2.difficulty,
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+
+    review_queue::enqueue(&app, &result.id, grade);
+    Ok(result)
+}
+
+/// What each grade would do to a card, without storing anything.
+#[tauri::command]
+pub async fn preview_intervals(app: AppHandle, card_id: String) -> Result<IntervalPreview, String> {
+    let path = db::path(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db::open_read_only(&path)?;
+        let ctx = Context::load(&conn)?;
+        let card = load_card(&conn, &card_id)?;
+        let days = |grade| card.grade(&ctx, grade).interval;
+        Ok(IntervalPreview {
+            again: days(Grade::Again),
+            hard: days(Grade::Hard),
+            good: days(Grade::Good),
+            easy: days(Grade::Easy),
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+pub async fn get_scheduler(app: AppHandle) -> Result<SchedulerSettings, String> {
+    let path = db::path(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db::open_read_only(&path)?;
+        scheduler_settings(&conn)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+pub async fn set_scheduler(
+    app: AppHandle,
+    scheduler: Scheduler,
+) -> Result<SchedulerSettings, String> {
+    let path = db::path(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db::open_read_write(&path)?;
+        set_setting(&conn, SCHEDULER_KEY, scheduler.as_str())?;
+        scheduler_settings(&conn)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Fit FSRS parameters to `review_log` and use them from now on.
+#[tauri::command]
+pub async fn optimize_fsrs(app: AppHandle) -> Result<Fit, String> {
+    let path = db::path(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db::open_read_write(&path)?;
+        let start = Context::load(&conn)?.parameters;
+        let fit = optimizer::optimize(start, &review_histories(&conn)?)?;
+        let json = serde_json::to_string(&fit.parameters).map_err(|err| err.to_string())?;
+        set_setting(&conn, FSRS_PARAMETERS_KEY, &json)?;
+        Ok(fit)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+fn load_card(conn: &Connection, card_id: &str) -> Result<Card, String> {
+    conn.query_row(
+        "SELECT difficulty, interval, repetitions, next_review,
+                stability, fsrs_difficulty, last_review
+         FROM flashcards WHERE id = ?1",
+        [card_id],
+        |row| {
+            // The table's defaults stand in for NULLs.
+            let interval: Option<i64> = row.get(1)?;
+            let repetitions: Option<i64> = row.get(2)?;
+            let sm2 = Sm2State {
+                difficulty: row.get::<_, Option<f64>>(0)?.unwrap_or(0.3),
+                interval: clamp_days(interval.unwrap_or(1)),
+                repetitions: clamp_days(repetitions.unwrap_or(0)),
+            };
+            let next_review: Option<String> = row.get(3)?;
+            let stability: Option<f64> = row.get(4)?;
+            let difficulty: Option<f64> = row.get(5)?;
+            let last_review: Option<String> = row.get(6)?;
+            Ok((sm2, next_review, stability, difficulty, last_review))
+        },
+    )
+    .optional()
+    .map_err(|err| err.to_string())?
+    .map(|(sm2, next_review, stability, difficulty, last_review)| {
+        let next_review = next_review.as_deref().and_then(parse_date);
+        let last_review = last_review.as_deref().and_then(parse_date);
+        match (stability, difficulty) {
+            (Some(stability), Some(difficulty)) => Card {
+                sm2,
+                memory: Some(MemoryState {
+                    stability: stability.max(fsrs::MIN_STABILITY),
+                    difficulty: difficulty.clamp(fsrs::MIN_DIFFICULTY, fsrs::MAX_DIFFICULTY),
+                }),
+                last_review,
+            },
+            // Last scheduled by the backend's SM-2: start FSRS from that.
+            _ => Card {
+                sm2,
+                memory: next_review.map(|_| fsrs::from_sm2(sm2.difficulty, sm2.interval)),
+                last_review: next_review
+                    .and_then(|day| day.checked_sub_days(Days::new(sm2.interval.into()))),
+            },
+        }
+    })
+    .ok_or_else(|| "Flashcard not found".to_string())
+}
+
+/// Every card's reviews, oldest first, with the days between them.
+fn review_histories(conn: &Connection) -> Result<Vec<Vec<Review>>, String> {
+    let mut stmt = conn
+        .prepare("SELECT card_id, grade, reviewed_on FROM review_log ORDER BY card_id, id")
+        .map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u8>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|err| err.to_string())?;
+
+    let mut histories: Vec<Vec<Review>> = Vec::new();
+    let mut previous: Option<(String, NaiveDate)> = None;
+    for row in rows {
+        let (card_id, grade, reviewed_on) = row.map_err(|err| err.to_string())?;
+        let (Ok(grade), Some(day)) = (Grade::try_from(grade), parse_date(&reviewed_on)) else {
+            continue;
+        };
+        let elapsed_days = match &previous {
+            Some((last_card, last_day)) if *last_card == card_id => {
+                clamp_days((day - *last_day).num_days())
+            }
+            _ => {
+                histories.push(Vec::new());
+                0
+            }
+        };
+        if let Some(history) = histories.last_mut() {
+            history.push(Review {
+                grade,
+                elapsed_days,
+            });
+        }
+        previous = Some((card_id, day));
+    }
+    Ok(histories)
+}
+
+fn scheduler_settings(conn: &Connection) -> Result<SchedulerSettings, String> {
+    let scheduler = Context::load(conn)?.scheduler;
+    let optimized = get_setting(conn, FSRS_PARAMETERS_KEY)?.is_some_and(|v| !v.is_empty());
+    let logged_reviews = conn
+        .query_row("SELECT COUNT(*) FROM review_log", [], |row| row.get(0))
+        .map_err(|err| err.to_string())?;
+    Ok(SchedulerSettings {
+        scheduler,
+        optimized,
+        logged_reviews,
+    })
+}
+
+fn get_setting(conn: &Connection, key: &str) -> Result<Option<String>, String> {
+    conn.query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
+        row.get(0)
+    })
+    .optional()
+    .map_err(|err| err.to_string())
+}
+
+/// Same upsert as the backend's `set_setting`.
+fn set_setting(conn: &Connection, key: &str, value: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO settings(key, value, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![
+            key,
+            value,
+            Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+        ],
+    )
+    .map(|_| ())
+    .map_err(|err| err.to_string())
+}
+
+/// `next_review`-style `YYYY-MM-DD`.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+fn clamp_days(value: i64) -> u32 {
+    u32::try_from(value.max(0)).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,200 @@
+//! Fitting FSRS parameters to the user's own review history.
+//!
+//! The loss is the log-loss of predicted retrievability against whether each
+//! review was actually recalled. A bounded coordinate descent minimizes it:
+//! derivative-free and plenty for the few thousand reviews a personal
+//! library produces.
+
+use serde::Serialize;
+
+use super::fsrs::{retrievability, Parameters, PARAMETER_BOUNDS, PARAMETER_COUNT};
+use super::Grade;
+
+/// Reviews that have to count towards the loss before we fit anything.
+pub const MIN_REVIEWS: usize = 64;
+
+const MAX_ROUNDS: usize = 200;
+/// Starting step, as a fraction of each parameter's range.
+const INITIAL_STEP: f64 = 0.05;
+/// Stop once every step has shrunk below this fraction of its range.
+const MIN_STEP: f64 = 1e-4;
+const EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Review {
+    pub grade: Grade,
+    /// Days since the previous review of the same card; unused for the first.
+    pub elapsed_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Fit {
+    pub parameters: Parameters,
+    /// Reviews the loss was computed over.
+    pub reviews: usize,
+    pub loss_before: f64,
+    pub loss_after: f64,
+}
+
+/// Mean log-loss of `params` over `histories` (one per card, oldest first),
+/// and how many reviews it covers. A card's first review only seeds its
+/// memory, and same-day repeats predict nothing.
+pub fn loss(params: &Parameters, histories: &[Vec<Review>]) -> (f64, usize) {
+    let mut total = 0.0;
+    let mut count = 0;
+    for history in histories {
+        let mut reviews = history.iter();
+        let Some(first) = reviews.next() else {
+            continue;
+        };
+        let mut state = params.init(first.grade);
+        for review in reviews {
+            let elapsed = f64::from(review.elapsed_days);
+            if review.elapsed_days > 0 {
+                let r = retrievability(elapsed, state.stability).clamp(EPSILON, 1.0 - EPSILON);
+                total -= if review.grade == Grade::Again {
+                    (1.0 - r).ln()
+                } else {
+                    r.ln()
+                };
+                count += 1;
+            }
+            state = params.review(state, review.grade, elapsed);
+        }
+    }
+    (total / count.max(1) as f64, count)
+}
+
+/// Improve on `start` (or the defaults, if `start` is out of bounds) for
+/// this history.
+pub fn optimize(start: Parameters, histories: &[Vec<Review>]) -> Result<Fit, String> {
+    let start = if start.is_valid() {
+        start
+    } else {
+        Parameters::default()
+    };
+    let (loss_before, reviews) = loss(&start, histories);
+    if reviews < MIN_REVIEWS {
+        return Err(format!(
+            "FSRS needs at least {MIN_REVIEWS} reviews made a day or more apart; there are {reviews} so far"
+        ));
+    }
+
+    let mut best = start;
+    let mut best_loss = loss_before;
+    let mut steps: [f64; PARAMETER_COUNT] =
+        PARAMETER_BOUNDS.map(|(lo, hi)| (hi - lo) * INITIAL_STEP);
+
+    for _ in 0..MAX_ROUNDS {
+        let mut improved = false;
+        for (i, step) in steps.iter().enumerate() {
+            let (lo, hi) = PARAMETER_BOUNDS[i];
+            for direction in [1.0, -1.0] {
+                let mut candidate = best;
+                candidate.0[i] = (best.0[i] + direction * step).clamp(lo, hi);
+                if candidate == best || !candidate.is_valid() {
+                    continue;
+                }
+                let (candidate_loss, _) = loss(&candidate, histories);
+                if candidate_loss < best_loss {
+                    best = candidate;
+                    best_loss = candidate_loss;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        if !improved {
+            for step in &mut steps {
+                *step /= 2.0;
+            }
+            let converged = steps
+                .iter()
+                .zip(PARAMETER_BOUNDS)
+                .all(|(step, (lo, hi))| *step < (hi - lo) * MIN_STEP);
+            if converged {
+                break;
+            }
+        }
+    }
+
+    Ok(Fit {
+        parameters: best,
+        reviews,
+        loss_before,
+        loss_after: best_loss,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn review(grade: Grade, elapsed_days: u32) -> Review {
+        Review {
+            grade,
+            elapsed_days,
+        }
+    }
+
+    #[test]
+    fn too_little_history_is_refused() {
+        let histories = vec![vec![review(Grade::Good, 0), review(Grade::Good, 3)]];
+        assert!(optimize(Parameters::default(), &histories).is_err());
+    }
+
+    #[test]
+    fn first_and_same_day_reviews_are_not_scored() {
+        let history = vec![
+            review(Grade::Good, 0),
+            review(Grade::Again, 0),
+            review(Grade::Good, 2),
+        ];
+        assert_eq!(loss(&Parameters::default(), &[history]).1, 1);
+    }
+
+    #[test]
+    fn learns_that_cards_are_forgotten_faster() {
+        // Every card is forgotten after a week but recalled after a day,
+        // far worse memory than the defaults assume.
+        let histories: Vec<_> = (0..40)
+            .map(|_| {
+                vec![
+                    review(Grade::Good, 0),
+                    review(Grade::Good, 1),
+                    review(Grade::Again, 7),
+                    review(Grade::Good, 1),
+                ]
+            })
+            .collect();
+        let fit = optimize(Parameters::default(), &histories).unwrap();
+        assert!(fit.loss_after < fit.loss_before);
+        assert!(fit.parameters.is_valid());
+    }
+
+    fn any_history() -> impl Strategy<Value = Vec<Review>> {
+        prop::collection::vec(
+            (prop::sample::select(Grade::ALL.to_vec()), 0u32..60)
+                .prop_map(|(grade, elapsed_days)| review(grade, elapsed_days)),
+            2..12,
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn never_worse_than_where_it_started(
+            histories in prop::collection::vec(any_history(), 20..40),
+        ) {
+            let Ok(fit) = optimize(Parameters::default(), &histories) else {
+                return Ok(());
+            };
+            prop_assert!(fit.loss_after <= fit.loss_before);
+            prop_assert!(fit.parameters.is_valid());
+            let (recomputed, _) = loss(&fit.parameters, &histories);
+            prop_assert!((recomputed - fit.loss_after).abs() < 1e-12);
+        }
+    }
+}
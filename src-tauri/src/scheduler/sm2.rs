@@ -0,0 +1,185 @@
+//! SM-2 exactly as the backend's `_compute_sm2` runs it
+//! (`backend/app/routers/quiz.py`). `difficulty` is the SM-2 ease factor
+//! rescaled to 0.1–0.9, lower meaning easier.
+
+use chrono::{Days, NaiveDate};
+
+use super::Grade;
+
+pub const MIN_DIFFICULTY: f64 = 0.1;
+pub const MAX_DIFFICULTY: f64 = 0.9;
+/// Longest interval we hand out, so `next_review` stays a real date.
+pub const MAX_INTERVAL_DAYS: u32 = 36_500;
+
+/// The `flashcards` columns SM-2 reads and writes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sm2State {
+    pub difficulty: f64,
+    pub interval: u32,
+    pub repetitions: u32,
+}
+
+impl Sm2State {
+    /// Where the card stands after being graded `grade`.
+    pub fn review(self, grade: Grade) -> Self {
+        let q = grade.quality();
+
+        let (repetitions, interval) = if q < 3 {
+            // Incorrect recall: reset streak and retry tomorrow
+            (0, 1)
+        } else {
+            let interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                // Interval grows faster for easy cards (low difficulty).
+                // Python's round() goes to even on ties; so do we.
+                _ => (f64::from(self.interval) * (2.5 - self.difficulty * 1.2))
+                    .round_ties_even()
+                    .clamp(1.0, f64::from(MAX_INTERVAL_DAYS)) as u32,
+            };
+            (self.repetitions.saturating_add(1), interval)
+        };
+
+        // EF-inspired difficulty update (standard SM-2 EF formula, rescaled)
+        let q = f64::from(q);
+        let ef_delta = 0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02);
+        let difficulty = (self.difficulty - ef_delta * 0.2).clamp(MIN_DIFFICULTY, MAX_DIFFICULTY);
+
+        Self {
+            difficulty,
+            interval,
+            repetitions,
+        }
+    }
+}
+
+/// The date a card reviewed on `today` comes back, `interval` days later.
+pub fn next_review(today: NaiveDate, interval: u32) -> NaiveDate {
+    today
+        .checked_add_days(Days::new(interval.into()))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn state(difficulty: f64, interval: u32, repetitions: u32) -> Sm2State {
+        Sm2State {
+            difficulty,
+            interval,
+            repetitions,
+        }
+    }
+
+    #[test]
+    fn new_card_graduates_one_then_six_days() {
+        let first = state(0.3, 1, 0).review(Grade::Good);
+        assert_eq!((first.repetitions, first.interval), (1, 1));
+        let second = first.review(Grade::Good);
+        assert_eq!((second.repetitions, second.interval), (2, 6));
+        let third = second.review(Grade::Good);
+        // 6 * (2.5 - 0.3 * 1.2) = 12.84
+        assert_eq!((third.repetitions, third.interval), (3, 13));
+    }
+
+    #[test]
+    fn difficulty_matches_backend() {
+        let cases = [
+            (Grade::Again, 0.46),
+            (Grade::Hard, 0.364),
+            (Grade::Good, 0.3),
+            (Grade::Easy, 0.28),
+        ];
+        for (grade, expected) in cases {
+            let next = state(0.3, 6, 2).review(grade);
+            assert!((next.difficulty - expected).abs() < 1e-9, "{grade:?}");
+        }
+    }
+
+    #[test]
+    fn rounds_half_to_even_like_python() {
+        // 15 * (2.5 - 0.5 * 1.2) = 28.5
+        assert_eq!(state(0.5, 15, 2).review(Grade::Good).interval, 28);
+    }
+
+    #[test]
+    fn next_review_saturates() {
+        assert_eq!(next_review(NaiveDate::MAX, 1), NaiveDate::MAX);
+    }
+
+    fn any_grade() -> impl Strategy<Value = Grade> {
+        prop::sample::select(Grade::ALL.to_vec())
+    }
+
+    fn any_state() -> impl Strategy<Value = Sm2State> {
+        (
+            MIN_DIFFICULTY..=MAX_DIFFICULTY,
+            1..=MAX_INTERVAL_DAYS,
+            0u32..1_000,
+        )
+            .prop_map(|(difficulty, interval, repetitions)| {
+                state(difficulty, interval, repetitions)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn stays_in_bounds(card in any_state(), grade in any_grade()) {
+            let next = card.review(grade);
+            prop_assert!((1..=MAX_INTERVAL_DAYS).contains(&next.interval));
+            prop_assert!((MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&next.difficulty));
+        }
+
+        #[test]
+        fn failing_resets_the_streak(
+            card in any_state(),
+            grade in prop::sample::select(vec![Grade::Again, Grade::Hard]),
+        ) {
+            let next = card.review(grade);
+            prop_assert_eq!((next.repetitions, next.interval), (0, 1));
+            prop_assert!(next.difficulty >= card.difficulty);
+        }
+
+        #[test]
+        fn passing_extends_the_streak(
+            card in any_state(),
+            grade in prop::sample::select(vec![Grade::Good, Grade::Easy]),
+        ) {
+            let next = card.review(grade);
+            prop_assert_eq!(next.repetitions, card.repetitions + 1);
+            prop_assert!(next.difficulty <= card.difficulty);
+        }
+
+        #[test]
+        fn better_grades_are_never_due_sooner(card in any_state()) {
+            let outcomes: Vec<_> = Grade::ALL.iter().map(|&g| card.review(g)).collect();
+            for pair in outcomes.windows(2) {
+                prop_assert!(pair[0].interval <= pair[1].interval);
+                prop_assert!(pair[0].difficulty >= pair[1].difficulty);
+            }
+        }
+
+        #[test]
+        fn mature_cards_never_shrink_on_success(
+            card in any_state(),
+            grade in prop::sample::select(vec![Grade::Good, Grade::Easy]),
+        ) {
+            prop_assume!(card.repetitions >= 2);
+            // 2.5 - 0.9 * 1.2 = 1.42 is the slowest growth SM-2 allows.
+            prop_assert!(card.review(grade).interval >= card.interval);
+        }
+
+        #[test]
+        fn next_review_is_interval_days_out(
+            days_from_epoch in 0i64..100_000,
+            interval in 0..=MAX_INTERVAL_DAYS,
+        ) {
+            let today = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+                + chrono::Duration::days(days_from_epoch);
+            let days = (next_review(today, interval) - today).num_days();
+            prop_assert_eq!(days, i64::from(interval));
+        }
+    }
+}
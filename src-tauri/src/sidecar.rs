@@ -0,0 +1,601 @@
+//! Supervision of the `pagenode-backend` sidecar.
+//!
+//! The sidecar prints `PORT=<n>` once uvicorn has picked a free port; it is
+//! considered ready once `/health` answers on that port. The supervisor
+//! records the port in [`BackendPort`], keeps draining the event
+//! stream, and respawns the process with exponential backoff whenever it
+//! terminates. Too many crashes inside [`CRASH_WINDOW`] stops the loop so a
+//! broken install doesn't spin forever.
+//!
+//! A sidecar that never completes the handshake (missing binary, Python
+//! import error, ...) is not retried: the state goes straight to
+//! [`BackendState::Failed`] with the tail of its stderr so the UI can show
+//! something actionable.
+//!
+//! Debug builds run `backend/main.py` with the checkout's venv interpreter
+//! instead of the bundled binary (see [`Launch`]), so `tauri dev` works
+//! without `scripts/dev.sh`.
+//!
+//! Everything the process writes is appended to `backend.log` (see
+//! [`crate::logs`]), along with the supervisor's own lifecycle notes.
+//!
+//! On quit, [`shutdown`] asks the backend to exit through `POST /shutdown`,
+//! waits [`SHUTDOWN_GRACE`] for it to flush Kuzu and SQLite, and only then
+//! kills it. [`pause`] stops it the same way but holds the supervisor until
+//! the returned guard is dropped, for work that needs the data directory to
+//! itself (see [`crate::backup`]).
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::{Command, CommandChild, CommandEvent, TerminatedPayload};
+use tauri_plugin_shell::ShellExt;
+use tokio::sync::watch;
+
+use crate::health;
+use crate::logs::{self, Logs};
+use crate::port::parse_port_line;
+use crate::proxy::LoopbackClient;
+use crate::token::{ApiToken, TOKEN_ENV, TOKEN_HEADER};
+use crate::BackendPort;
+
+const SIDECAR_NAME: &str = "pagenode-backend";
+
+/// Event emitted to the webview on every supervisor state change.
+pub const STATUS_EVENT: &str = "backend-status";
+
+/// Overrides [`DEFAULT_HANDSHAKE_TIMEOUT`], in seconds.
+const HANDSHAKE_TIMEOUT_ENV: &str = "PAGENODE_HANDSHAKE_TIMEOUT_SECS";
+/// The PyInstaller build unpacks itself and imports every dependency before
+/// printing `PORT=`, which can take a while on a cold disk.
+const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(60);
+const STDERR_TAIL_LINES: usize = 40;
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Set to `0` to make a debug build use the bundled sidecar binary.
+const DEV_SIDECAR_ENV: &str = "PAGENODE_DEV_SIDECAR";
+/// Interpreter for the dev backend; defaults to `backend/.venv`.
+const DEV_PYTHON_ENV: &str = "PAGENODE_DEV_PYTHON";
+/// Entry script for the dev backend; defaults to `backend/main.py`.
+const DEV_BACKEND_ENV: &str = "PAGENODE_DEV_BACKEND";
+#[cfg(windows)]
+const VENV_PYTHON: &str = ".venv/Scripts/python.exe";
+#[cfg(not(windows))]
+const VENV_PYTHON: &str = ".venv/bin/python";
+
+const BACKOFF_INITIAL: Duration = Duration::from_millis(500);
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// Crashes older than this no longer count towards the crash-loop cap.
+const CRASH_WINDOW: Duration = Duration::from_secs(120);
+const MAX_CRASHES_IN_WINDOW: usize = 5;
+
+/// How long the backend gets to finish in-flight work after `/shutdown`.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(8);
+const SHUTDOWN_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum BackendState {
+    Starting,
+    Ready {
+        port: u16,
+    },
+    Restarting {
+        attempt: u32,
+        delay_ms: u64,
+    },
+    Failed {
+        reason: String,
+        stderr_tail: Vec<String>,
+    },
+    /// Stopped on purpose by [`pause`].
+    Paused,
+}
+
+pub struct SidecarState {
+    /// Latest supervisor state, readable through `get_backend_state`.
+    state: Mutex<BackendState>,
+    /// Handle of the running process; taken by whoever has to kill it.
+    child: Mutex<Option<CommandChild>>,
+    /// `true` while a sidecar process is running.
+    alive: watch::Sender<bool>,
+    /// `true` while a [`Paused`] guard holds the supervisor.
+    paused: watch::Sender<bool>,
+    shutting_down: AtomicBool,
+}
+
+impl Default for SidecarState {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(BackendState::Starting),
+            child: Mutex::new(None),
+            alive: watch::Sender::new(false),
+            paused: watch::Sender::new(false),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+}
+
+impl SidecarState {
+    pub fn current(&self) -> BackendState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Mark the app as quitting. Returns `true` only for the first caller.
+    pub fn begin_shutdown(&self) -> bool {
+        !self.shutting_down.swap(true, Ordering::SeqCst)
+    }
+
+    fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Returns once no [`Paused`] guard is held.
+    async fn wait_unpaused(&self) {
+        let _ = self.paused.subscribe().wait_for(|paused| !*paused).await;
+    }
+
+    fn attach(&self, child: CommandChild) {
+        *self.child.lock().unwrap() = Some(child);
+        self.alive.send_replace(true);
+    }
+
+    fn detach(&self) {
+        self.child.lock().unwrap().take();
+        self.alive.send_replace(false);
+    }
+
+    /// Kill the running process; the supervisor treats it as a crash.
+    pub fn kill(&self) {
+        if let Some(child) = self.child.lock().unwrap().take() {
+            if let Err(err) = child.kill() {
+                eprintln!("[pagenode] failed to kill backend sidecar: {err}");
+            }
+        }
+    }
+}
+
+/// Where the backend process comes from.
+#[derive(Debug, Clone)]
+pub enum Launch {
+    /// The PyInstaller binary shipped as `externalBin`.
+    Bundled,
+    /// `python main.py` straight from the source checkout, for `tauri dev`.
+    Dev { python: PathBuf, script: PathBuf },
+}
+
+impl Launch {
+    /// Debug builds run the backend from source unless [`DEV_SIDECAR_ENV`] is `0`.
+    pub fn detect() -> Self {
+        if !cfg!(debug_assertions) || std::env::var(DEV_SIDECAR_ENV).is_ok_and(|v| v == "0") {
+            return Launch::Bundled;
+        }
+        let backend_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../backend");
+        let python = std::env::var_os(DEV_PYTHON_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| backend_dir.join(VENV_PYTHON));
+        let script = std::env::var_os(DEV_BACKEND_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| backend_dir.join("main.py"));
+        Launch::Dev { python, script }
+    }
+
+    fn command(&self, handle: &AppHandle) -> Result<Command, String> {
+        match self {
+            Launch::Bundled => handle
+                .shell()
+                .sidecar(SIDECAR_NAME)
+                .map_err(|err| format!("sidecar binary not found: {err}")),
+            Launch::Dev { python, script } => {
+                if !python.exists() {
+                    return Err(format!(
+                        "dev backend interpreter not found at {} (set {DEV_PYTHON_ENV})",
+                        python.display()
+                    ));
+                }
+                let mut command = handle
+                    .shell()
+                    .command(python)
+                    .arg(script)
+                    .env("PYTHONUNBUFFERED", "1");
+                if let Some(dir) = script.parent() {
+                    command = command.current_dir(dir);
+                }
+                Ok(command)
+            }
+        }
+    }
+
+    /// Dev backends mirror their output to the terminal running `tauri dev`.
+    fn echoes_output(&self) -> bool {
+        matches!(self, Launch::Dev { .. })
+    }
+}
+
+impl fmt::Display for Launch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Launch::Bundled => f.write_str("sidecar"),
+            Launch::Dev { script, .. } => write!(f, "dev backend {}", script.display()),
+        }
+    }
+}
+
+/// How a single sidecar run ended.
+enum Exit {
+    /// Never printed `PORT=`; retrying is unlikely to help.
+    StartupFailed {
+        reason: String,
+        stderr_tail: Vec<String>,
+    },
+    /// Was serving requests, then went away.
+    Crashed {
+        reason: String,
+        stderr_tail: Vec<String>,
+    },
+}
+
+pub fn set_state(handle: &AppHandle, state: BackendState) {
+    let port = match state {
+        BackendState::Ready { port } => port,
+        _ => 0,
+    };
+    handle.state::<BackendPort>().set(port);
+    *handle.state::<SidecarState>().state.lock().unwrap() = state.clone();
+    if let Err(err) = handle.emit(STATUS_EVENT, &state) {
+        logs::shell(handle, &format!("failed to emit {STATUS_EVENT}: {err}"));
+    }
+}
+
+/// Print a supervisor message and record it in the backend log.
+fn note(handle: &AppHandle, message: &str) {
+    eprintln!("[pagenode] {message}");
+    handle.state::<Logs>().backend.append("supervisor", message);
+}
+
+fn handshake_timeout() -> Duration {
+    std::env::var(HANDSHAKE_TIMEOUT_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_HANDSHAKE_TIMEOUT)
+}
+
+fn backoff_delay(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    BACKOFF_INITIAL.saturating_mul(factor).min(BACKOFF_MAX)
+}
+
+fn describe(payload: &TerminatedPayload) -> String {
+    match (payload.code, payload.signal) {
+        (Some(code), _) => format!("exited with code {code}"),
+        (None, Some(signal)) => format!("killed by signal {signal}"),
+        (None, None) => "terminated".to_string(),
+    }
+}
+
+/// Start the supervisor loop on the async runtime.
+pub fn supervise(handle: AppHandle, launch: Launch) {
+    tauri::async_runtime::spawn(async move {
+        let mut crashes: VecDeque<Instant> = VecDeque::new();
+        let sidecar = handle.state::<SidecarState>();
+
+        loop {
+            sidecar.wait_unpaused().await;
+            if sidecar.is_shutting_down() {
+                return;
+            }
+            set_state(&handle, BackendState::Starting);
+
+            let exit = run_once(&handle, &launch).await;
+            sidecar.detach();
+            if sidecar.is_shutting_down() {
+                return;
+            }
+            // Stopped by `pause`, not a crash.
+            if sidecar.is_paused() {
+                continue;
+            }
+
+            let (reason, stderr_tail) = match exit {
+                Exit::StartupFailed {
+                    reason,
+                    stderr_tail,
+                } => {
+                    note(
+                        &handle,
+                        &format!("backend sidecar failed to start: {reason}"),
+                    );
+                    set_state(
+                        &handle,
+                        BackendState::Failed {
+                            reason,
+                            stderr_tail,
+                        },
+                    );
+                    return;
+                }
+                Exit::Crashed {
+                    reason,
+                    stderr_tail,
+                } => (reason, stderr_tail),
+            };
+            note(&handle, &format!("backend sidecar {reason}"));
+
+            let now = Instant::now();
+            crashes.push_back(now);
+            while crashes
+                .front()
+                .is_some_and(|t| now.duration_since(*t) > CRASH_WINDOW)
+            {
+                crashes.pop_front();
+            }
+            if crashes.len() > MAX_CRASHES_IN_WINDOW {
+                set_state(
+                    &handle,
+                    BackendState::Failed {
+                        reason: format!(
+                            "backend crashed {} times in {}s (last: {reason})",
+                            crashes.len(),
+                            CRASH_WINDOW.as_secs()
+                        ),
+                        stderr_tail,
+                    },
+                );
+                return;
+            }
+
+            let attempt = crashes.len() as u32;
+            let delay = backoff_delay(attempt);
+            set_state(
+                &handle,
+                BackendState::Restarting {
+                    attempt,
+                    delay_ms: delay.as_millis() as u64,
+                },
+            );
+            tokio::time::sleep(delay).await;
+        }
+    });
+}
+
+/// Spawn the sidecar once and drive its event stream until it exits.
+async fn run_once(handle: &AppHandle, launch: &Launch) -> Exit {
+    // Every spawn gets a new token so a crashed process's secret is useless.
+    let token = match handle.state::<ApiToken>().rotate() {
+        Ok(token) => token,
+        Err(reason) => {
+            return Exit::StartupFailed {
+                reason,
+                stderr_tail: Vec::new(),
+            }
+        }
+    };
+    let command = match launch.command(handle) {
+        Ok(command) => command.env(TOKEN_ENV, &token),
+        Err(reason) => {
+            return Exit::StartupFailed {
+                reason,
+                stderr_tail: Vec::new(),
+            }
+        }
+    };
+    let (mut rx, child) = match command.spawn() {
+        Ok(spawned) => spawned,
+        Err(err) => {
+            return Exit::StartupFailed {
+                reason: format!("failed to spawn {launch}: {err}"),
+                stderr_tail: Vec::new(),
+            }
+        }
+    };
+
+    let sidecar = handle.state::<SidecarState>();
+    sidecar.attach(child);
+
+    let timeout = handshake_timeout();
+    let handshake = tokio::time::sleep(timeout);
+    tokio::pin!(handshake);
+    let client = handle.state::<LoopbackClient>().client().clone();
+    let mut stderr_tail: VecDeque<String> = VecDeque::with_capacity(STDERR_TAIL_LINES);
+    // Port announced on stdout, waiting for `/health` to answer.
+    let mut pending: Option<(u16, HealthProbe)> = None;
+    let mut ready = false;
+    let logs = handle.state::<Logs>();
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(event) = event else { break };
+                match event {
+                    CommandEvent::Stdout(line_bytes) => {
+                        let line = String::from_utf8_lossy(&line_bytes);
+                        logs.backend.append("stdout", &line);
+                        if launch.echoes_output() {
+                            println!("[backend] {}", line.trim_end());
+                        }
+                        if ready || pending.is_some() {
+                            continue;
+                        }
+                        if let Some(port) = parse_port_line(&line) {
+                            let probe = wait_healthy(client.clone(), port, token.clone());
+                            pending = Some((port, Box::pin(probe)));
+                        }
+                    }
+                    CommandEvent::Stderr(line_bytes) => {
+                        let line = String::from_utf8_lossy(&line_bytes);
+                        let line = line.trim_end();
+                        logs.backend.append("stderr", line);
+                        if launch.echoes_output() {
+                            eprintln!("[backend] {line}");
+                        }
+                        if stderr_tail.len() == STDERR_TAIL_LINES {
+                            stderr_tail.pop_front();
+                        }
+                        stderr_tail.push_back(line.to_string());
+                    }
+                    CommandEvent::Error(err) => {
+                        note(handle, &format!("backend sidecar error: {err}"));
+                    }
+                    CommandEvent::Terminated(payload) => {
+                        let reason = describe(&payload);
+                        let stderr_tail = stderr_tail.into();
+                        return if ready {
+                            Exit::Crashed {
+                                reason,
+                                stderr_tail,
+                            }
+                        } else {
+                            Exit::StartupFailed {
+                                reason: format!("{launch} {reason} before becoming healthy"),
+                                stderr_tail,
+                            }
+                        };
+                    }
+                    _ => {}
+                }
+            }
+            _ = async { pending.as_mut().unwrap().1.as_mut().await }, if pending.is_some() => {
+                let (port, _) = pending.take().unwrap();
+                ready = true;
+                set_state(handle, BackendState::Ready { port });
+            }
+            _ = &mut handshake, if !ready => {
+                sidecar.kill();
+                let reason = match &pending {
+                    Some((port, _)) => format!(
+                        "backend on port {port} did not answer /health within {}s",
+                        timeout.as_secs()
+                    ),
+                    None => format!("no PORT= handshake within {}s", timeout.as_secs()),
+                };
+                return Exit::StartupFailed {
+                    reason,
+                    stderr_tail: stderr_tail.into(),
+                };
+            }
+        }
+    }
+
+    // The channel only closes after the process is gone.
+    let reason = "event stream closed".to_string();
+    let stderr_tail = stderr_tail.into();
+    if ready {
+        Exit::Crashed {
+            reason,
+            stderr_tail,
+        }
+    } else {
+        Exit::StartupFailed {
+            reason,
+            stderr_tail,
+        }
+    }
+}
+
+type HealthProbe = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Poll `/health` until it answers; bounded by the caller's handshake deadline.
+async fn wait_healthy(client: reqwest::Client, port: u16, token: String) {
+    while health::probe(&client, port, &token, HEALTH_PROBE_TIMEOUT)
+        .await
+        .is_err()
+    {
+        tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+    }
+}
+
+/// Keeps the backend stopped until dropped; see [`pause`].
+pub struct Paused {
+    handle: AppHandle,
+    /// A supervisor that had given up is gone and won't restart anything.
+    prior: BackendState,
+}
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        if matches!(self.prior, BackendState::Failed { .. }) {
+            set_state(&self.handle, self.prior.clone());
+        }
+        self.handle
+            .state::<SidecarState>()
+            .paused
+            .send_replace(false);
+    }
+}
+
+/// Stop the sidecar gracefully and keep the supervisor from restarting it
+/// until the guard is dropped.
+pub async fn pause(handle: &AppHandle) -> Result<Paused, String> {
+    let sidecar = handle.state::<SidecarState>();
+    let prior = sidecar.current();
+    // dev.sh's backend is ready without being our child, and not ours to stop.
+    if matches!(prior, BackendState::Ready { .. }) && !*sidecar.alive.borrow() {
+        return Err("The backend is running outside PageNode; stop it first.".to_string());
+    }
+    if sidecar.paused.send_replace(true) {
+        return Err("Another backup or restore is already running.".to_string());
+    }
+    let guard = Paused {
+        handle: handle.clone(),
+        prior,
+    };
+    note(handle, "pausing backend");
+    shutdown(handle).await;
+    set_state(handle, BackendState::Paused);
+    Ok(guard)
+}
+
+/// Stop the sidecar gracefully, killing it if it outlives [`SHUTDOWN_GRACE`].
+///
+/// Callers must have won [`SidecarState::begin_shutdown`] or hold a [`Paused`]
+/// first so the supervisor doesn't restart the process as it exits.
+pub async fn shutdown(handle: &AppHandle) {
+    let sidecar = handle.state::<SidecarState>();
+    let mut alive = sidecar.alive.subscribe();
+    if !*alive.borrow() {
+        return;
+    }
+
+    if let Some(port) = handle.state::<BackendPort>().get() {
+        let result = handle
+            .state::<LoopbackClient>()
+            .client()
+            .post(format!("http://127.0.0.1:{port}/shutdown"))
+            .header(TOKEN_HEADER, handle.state::<ApiToken>().get())
+            .timeout(SHUTDOWN_REQUEST_TIMEOUT)
+            .send()
+            .await;
+        if let Err(err) = result {
+            note(handle, &format!("backend /shutdown request failed: {err}"));
+        }
+    }
+
+    let exited = async { alive.wait_for(|alive| !*alive).await.is_ok() };
+    if !tokio::time::timeout(SHUTDOWN_GRACE, exited)
+        .await
+        .unwrap_or(false)
+    {
+        note(
+            handle,
+            &format!(
+                "backend still running after {}s, killing it",
+                SHUTDOWN_GRACE.as_secs()
+            ),
+        );
+        sidecar.kill();
+    }
+}
@@ -0,0 +1,35 @@
+//! Per-launch shared secret between the shell and the backend.
+//!
+//! A fresh token is minted before every sidecar spawn and handed to it through
+//! [`TOKEN_ENV`]; the backend rejects any request whose [`TOKEN_HEADER`] does
+//! not match. The `pagenode-api://` proxy attaches it to every forwarded
+//! request, so the secret never reaches the webview.
+
+use std::sync::Mutex;
+
+/// Environment variable the backend reads its expected token from.
+pub const TOKEN_ENV: &str = "PAGENODE_API_TOKEN";
+/// Header every request to the backend must carry.
+pub const TOKEN_HEADER: &str = "X-PageNode-Token";
+
+#[derive(Default)]
+pub struct ApiToken(Mutex<String>);
+
+impl ApiToken {
+    pub fn get(&self) -> String {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, token: String) {
+        *self.0.lock().unwrap() = token;
+    }
+
+    /// Replace the token with a new random one and return it.
+    pub fn rotate(&self) -> Result<String, String> {
+        let mut bytes = [0u8; 32];
+        getrandom::fill(&mut bytes).map_err(|err| format!("no OS randomness: {err}"))?;
+        let token: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        self.set(token.clone());
+        Ok(token)
+    }
+}
@@ -0,0 +1,171 @@
+//! Tray icon: how many flashcards are due, and a way back into the app.
+//!
+//! The count is read from SQLite (see [`crate::library`]), so it shows while
+//! the backend is still starting, and is refreshed every
+//! [`DUE_REFRESH_INTERVAL`]. It also drives the daily due-cards notification.
+//! With `keep_running_in_background` set, closing the main window only hides
+//! it, so the tray (and the sidecar behind it) stays around until "Quit".
+
+use std::time::Duration;
+
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Listener, Manager, WindowEvent, Wry};
+
+use crate::prefs::{self, Prefs, ShellPrefs, PREFS_EVENT};
+use crate::{import, instance, library, logs, notify, ui_events};
+
+const TRAY_ID: &str = "main";
+const DUE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+const REVIEW_ID: &str = "review";
+const IMPORT_ID: &str = "import";
+const LIBRARY_ID: &str = "library";
+const BACKGROUND_ID: &str = "background";
+const QUIT_ID: &str = "quit";
+
+/// Menu items whose text or state changes after the tray is built.
+pub struct Tray {
+    icon: TrayIcon<Wry>,
+    due: MenuItem<Wry>,
+    background: CheckMenuItem<Wry>,
+}
+
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let keep_running = app.state::<Prefs>().get().keep_running_in_background;
+
+    let due = MenuItem::with_id(app, "due", "Checking due cards…", false, None::<&str>)?;
+    let background = CheckMenuItem::with_id(
+        app,
+        BACKGROUND_ID,
+        "Keep running when window is closed",
+        true,
+        keep_running,
+        None::<&str>,
+    )?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &due,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, REVIEW_ID, "Start review", true, None::<&str>)?,
+            &MenuItem::with_id(app, IMPORT_ID, "Import document…", true, None::<&str>)?,
+            &MenuItem::with_id(app, LIBRARY_ID, "Open library", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &background,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, QUIT_ID, "Quit PageNode", true, None::<&str>)?,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("PageNode")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                instance::focus_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let icon = builder.build(app)?;
+
+    app.manage(Tray {
+        icon,
+        due,
+        background,
+    });
+
+    // Settings page and tray share the same preference.
+    let handle = app.clone();
+    app.listen(PREFS_EVENT, move |event| {
+        if let Ok(prefs) = serde_json::from_str::<ShellPrefs>(event.payload()) {
+            let _ = handle
+                .state::<Tray>()
+                .background
+                .set_checked(prefs.keep_running_in_background);
+        }
+    });
+
+    if let Some(window) = app.get_webview_window("main") {
+        let handle = app.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                if handle.state::<Prefs>().get().keep_running_in_background {
+                    api.prevent_close();
+                    if let Some(window) = handle.get_webview_window("main") {
+                        let _ = window.hide();
+                    }
+                }
+            }
+        });
+    }
+
+    refresh_due_count(app.clone());
+    Ok(())
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        REVIEW_ID => {
+            instance::focus_main_window(app);
+            ui_events::navigate(app, "/quiz?session=due");
+        }
+        LIBRARY_ID => {
+            instance::focus_main_window(app);
+            ui_events::navigate(app, "/");
+        }
+        IMPORT_ID => import::pick_documents(app),
+        BACKGROUND_ID => {
+            let checked = app.state::<Tray>().background.is_checked().unwrap_or(false);
+            if let Err(err) = prefs::update(app, |p| p.keep_running_in_background = checked) {
+                logs::shell(app, &err);
+            }
+        }
+        QUIT_ID => app.exit(0),
+        _ => {}
+    }
+}
+
+fn due_label(total: usize) -> String {
+    match total {
+        0 => "No cards due".to_string(),
+        1 => "1 card due".to_string(),
+        n => format!("{n} cards due"),
+    }
+}
+
+async fn fetch_due_count(app: &AppHandle) -> Result<usize, String> {
+    let total = library::read(app, library::count_due).await?;
+    Ok(usize::try_from(total).unwrap_or(usize::MAX))
+}
+
+/// Keep the due count current for the life of the app.
+fn refresh_due_count(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let tray = app.state::<Tray>();
+            match fetch_due_count(&app).await {
+                Ok(total) => {
+                    let label = due_label(total);
+                    let _ = tray.due.set_text(&label);
+                    let _ = tray.icon.set_tooltip(Some(format!("PageNode — {label}")));
+                    notify::due_cards(&app, total, &label);
+                }
+                Err(_) => {
+                    let _ = tray.due.set_text("Due cards unavailable");
+                    let _ = tray.icon.set_tooltip(Some("PageNode"));
+                }
+            }
+            tokio::time::sleep(DUE_REFRESH_INTERVAL).await;
+        }
+    });
+}
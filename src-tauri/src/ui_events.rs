@@ -0,0 +1,81 @@
+//! Shell → webview events that must not be lost while the webview loads.
+//!
+//! Files opened from the OS or deep links can arrive before React has
+//! registered any listener. [`emit`] queues such events until the frontend
+//! calls `ui_ready`, which returns the backlog and switches to plain emits.
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::logs;
+
+/// Route change requested by the shell, e.g. `{ "route": "/" }`.
+pub const NAVIGATE_EVENT: &str = "navigate";
+
+#[derive(Debug, Clone, Serialize)]
+struct Navigate {
+    route: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedEvent {
+    pub event: String,
+    pub payload: Value,
+}
+
+pub struct UiEvents {
+    /// `None` once the frontend is listening.
+    queue: Mutex<Option<Vec<QueuedEvent>>>,
+}
+
+impl Default for UiEvents {
+    fn default() -> Self {
+        Self {
+            queue: Mutex::new(Some(Vec::new())),
+        }
+    }
+}
+
+/// Emit to every window, or hold the event until the frontend is ready.
+pub fn emit<S: Serialize>(app: &AppHandle, event: &str, payload: S) {
+    let payload = match serde_json::to_value(payload) {
+        Ok(payload) => payload,
+        Err(err) => {
+            logs::shell(app, &format!("failed to serialize {event}: {err}"));
+            return;
+        }
+    };
+    let state = app.state::<UiEvents>();
+    let mut queue = state.queue.lock().unwrap();
+    match queue.as_mut() {
+        Some(pending) => pending.push(QueuedEvent {
+            event: event.to_string(),
+            payload,
+        }),
+        None => {
+            if let Err(err) = app.emit(event, payload) {
+                logs::shell(app, &format!("failed to emit {event}: {err}"));
+            }
+        }
+    }
+}
+
+/// Send the frontend to `route`, queued like any other event.
+pub fn navigate(app: &AppHandle, route: impl Into<String>) {
+    emit(
+        app,
+        NAVIGATE_EVENT,
+        Navigate {
+            route: route.into(),
+        },
+    );
+}
+
+/// Called once the frontend has registered its listeners.
+#[tauri::command]
+pub fn ui_ready(state: State<UiEvents>) -> Vec<QueuedEvent> {
+    state.queue.lock().unwrap().take().unwrap_or_default()
+}
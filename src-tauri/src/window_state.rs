@@ -0,0 +1,360 @@
+//! Main window geometry and last page, remembered across launches.
+//!
+//! Saved to `~/.pagenode/window.json` when the main window closes or the app
+//! exits, and applied in `setup` before the (initially hidden) window is
+//! shown. Geometry is kept in physical pixels together with the monitor it was
+//! on; if that monitor is gone, or the window would end up off-screen, it is
+//! moved and shrunk onto a monitor that is still connected.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, LogicalSize, Manager, Monitor, PhysicalPosition, PhysicalSize, State, WebviewWindow,
+    WindowEvent,
+};
+
+use crate::{logs, paths, ui_events};
+
+pub const WINDOW_STATE_FILENAME: &str = "window.json";
+
+/// Routes worth returning to; anything else (capture, deep-link queries)
+/// starts on the library.
+const RESTORABLE_ROUTES: &[&str] = &["/", "/graph", "/quiz", "/settings"];
+/// How much of the window must overlap a monitor to count as on-screen.
+const MIN_VISIBLE: i64 = 100;
+/// Matches the window size in `tauri.conf.json`.
+const DEFAULT_SIZE: LogicalSize<f64> = LogicalSize::new(1280.0, 800.0);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedWindow {
+    /// Normal (un-maximized) outer position and inner size.
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub monitor: Option<String>,
+    pub route: Option<String>,
+}
+
+/// What will be written on close; the route is reported by the frontend.
+pub struct WindowState {
+    path: PathBuf,
+    current: Mutex<Option<SavedWindow>>,
+}
+
+impl WindowState {
+    fn load(path: PathBuf) -> Self {
+        let current = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok());
+        Self {
+            path,
+            current: Mutex::new(current),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: i64,
+    y: i64,
+    width: i64,
+    height: i64,
+}
+
+impl Rect {
+    fn overlap(&self, other: &Rect) -> (i64, i64) {
+        let w = (self.x + self.width).min(other.x + other.width) - self.x.max(other.x);
+        let h = (self.y + self.height).min(other.y + other.height) - self.y.max(other.y);
+        (w.max(0), h.max(0))
+    }
+
+    /// Shrink to fit `screen`, then slide inside it.
+    fn clamp_into(self, screen: &Rect) -> Rect {
+        let width = self.width.min(screen.width);
+        let height = self.height.min(screen.height);
+        Rect {
+            x: self.x.clamp(screen.x, screen.x + screen.width - width),
+            y: self.y.clamp(screen.y, screen.y + screen.height - height),
+            width,
+            height,
+        }
+    }
+
+    fn centered_in(screen: &Rect, width: i64, height: i64) -> Rect {
+        let width = width.min(screen.width);
+        let height = height.min(screen.height);
+        Rect {
+            x: screen.x + (screen.width - width) / 2,
+            y: screen.y + (screen.height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+/// What [`place`] needs to know about a connected monitor.
+#[derive(Debug, Clone, PartialEq)]
+struct Screen {
+    name: Option<String>,
+    work_area: Rect,
+}
+
+impl Screen {
+    fn of(monitor: &Monitor) -> Self {
+        let area = monitor.work_area();
+        Self {
+            name: monitor.name().cloned(),
+            work_area: Rect {
+                x: area.position.x.into(),
+                y: area.position.y.into(),
+                width: area.size.width.into(),
+                height: area.size.height.into(),
+            },
+        }
+    }
+}
+
+/// Where `saved` should go given the monitors connected now.
+fn place(saved: &SavedWindow, screens: &[Screen], primary: Option<&Screen>) -> Option<Rect> {
+    let wanted = Rect {
+        x: saved.x.into(),
+        y: saved.y.into(),
+        width: saved.width.into(),
+        height: saved.height.into(),
+    };
+    if wanted.width <= 0 || wanted.height <= 0 {
+        return None;
+    }
+
+    // Same monitor if it's still there, else whichever shows most of it.
+    let same = saved
+        .monitor
+        .as_ref()
+        .and_then(|name| screens.iter().find(|s| s.name.as_ref() == Some(name)));
+    let best = screens
+        .iter()
+        .map(|s| (s, wanted.overlap(&s.work_area)))
+        .filter(|(_, (w, h))| *w >= MIN_VISIBLE && *h >= MIN_VISIBLE)
+        .max_by_key(|(_, (w, h))| w * h)
+        .map(|(s, _)| s);
+
+    match same.or(best) {
+        Some(screen) => Some(wanted.clamp_into(&screen.work_area)),
+        None => primary
+            .or(screens.first())
+            .map(|s| Rect::centered_in(&s.work_area, wanted.width, wanted.height)),
+    }
+}
+
+fn main_window(app: &AppHandle) -> Option<WebviewWindow> {
+    app.get_webview_window("main")
+}
+
+/// Apply the saved state (if any) and show the main window.
+pub fn restore(app: &AppHandle) -> tauri::Result<()> {
+    let path = paths::pagenode_home(app)?.join(WINDOW_STATE_FILENAME);
+    app.manage(WindowState::load(path));
+    let Some(window) = main_window(app) else {
+        return Ok(());
+    };
+
+    let saved = app.state::<WindowState>().current.lock().unwrap().clone();
+    if let Some(saved) = saved {
+        let screens: Vec<Screen> = window
+            .available_monitors()
+            .unwrap_or_default()
+            .iter()
+            .map(Screen::of)
+            .collect();
+        let primary = window
+            .primary_monitor()
+            .ok()
+            .flatten()
+            .map(|m| Screen::of(&m));
+        if let Some(rect) = place(&saved, &screens, primary.as_ref()) {
+            let _ = window.set_size(PhysicalSize::new(rect.width as u32, rect.height as u32));
+            let _ = window.set_position(PhysicalPosition::new(rect.x as i32, rect.y as i32));
+        }
+        if saved.maximized {
+            let _ = window.maximize();
+        }
+        if let Some(route) = saved
+            .route
+            .filter(|r| RESTORABLE_ROUTES.contains(&r.as_str()))
+        {
+            if route != "/" {
+                ui_events::navigate(app, route);
+            }
+        }
+    }
+
+    let handle = app.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::CloseRequested { .. } = event {
+            save(&handle);
+        }
+    });
+    window.show()
+}
+
+/// Capture the main window's geometry and write the state file.
+pub fn save(app: &AppHandle) {
+    let (Some(window), Some(state)) = (main_window(app), app.try_state::<WindowState>()) else {
+        return;
+    };
+    let mut current = state.current.lock().unwrap();
+    let mut saved = current.clone().unwrap_or_default();
+
+    let maximized = window.is_maximized().unwrap_or(false);
+    let minimized = window.is_minimized().unwrap_or(false);
+    saved.maximized = maximized;
+    // A maximized or minimized window's geometry isn't the one to come back to.
+    if !maximized && !minimized {
+        if let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) {
+            saved.x = position.x;
+            saved.y = position.y;
+            saved.width = size.width;
+            saved.height = size.height;
+        }
+    }
+    if let Ok(Some(monitor)) = window.current_monitor() {
+        saved.monitor = monitor.name().cloned();
+    }
+
+    let written = serde_json::to_vec_pretty(&saved)
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| {
+            if let Some(dir) = state.path.parent() {
+                fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+            }
+            fs::write(&state.path, bytes).map_err(|err| err.to_string())
+        });
+    if let Err(err) = written {
+        logs::shell(app, &format!("failed to save window state: {err}"));
+    }
+    *current = Some(saved);
+}
+
+/// Called by the frontend on every route change.
+#[tauri::command]
+pub fn set_last_route(state: State<WindowState>, route: String) {
+    let mut current = state.current.lock().unwrap();
+    current.get_or_insert_with(SavedWindow::default).route = Some(route);
+}
+
+/// Forget the saved state and put the main window back to its defaults.
+#[tauri::command]
+pub fn reset_window_state(app: AppHandle, state: State<WindowState>) -> Result<(), String> {
+    *state.current.lock().unwrap() = None;
+    match fs::remove_file(&state.path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(format!("failed to remove {}: {err}", state.path.display())),
+    }
+    let Some(window) = main_window(&app) else {
+        return Ok(());
+    };
+    window.unmaximize().map_err(|err| err.to_string())?;
+    window
+        .set_size(DEFAULT_SIZE)
+        .map_err(|err| err.to_string())?;
+    window.center().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i64, y: i64, width: i64, height: i64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn screen(name: &str, work_area: Rect) -> Screen {
+        Screen {
+            name: Some(name.to_string()),
+            work_area,
+        }
+    }
+
+    fn saved(monitor: &str, x: i32, y: i32, width: u32, height: u32) -> SavedWindow {
+        SavedWindow {
+            x,
+            y,
+            width,
+            height,
+            monitor: Some(monitor.to_string()),
+            ..SavedWindow::default()
+        }
+    }
+
+    /// A 1920x1080 laptop panel with a 40px taskbar, and a 2560x1440
+    /// external display to its right.
+    fn desk() -> Vec<Screen> {
+        vec![
+            screen("eDP-1", rect(0, 0, 1920, 1040)),
+            screen("DP-1", rect(1920, 0, 2560, 1400)),
+        ]
+    }
+
+    #[test]
+    fn fully_visible_window_stays_put() {
+        let screens = desk();
+        let placed = place(&saved("DP-1", 2100, 200, 1280, 800), &screens, None);
+        assert_eq!(placed, Some(rect(2100, 200, 1280, 800)));
+    }
+
+    #[test]
+    fn window_on_an_unplugged_monitor_is_centred_on_the_primary() {
+        let screens = desk()[..1].to_vec();
+        let placed = place(
+            &saved("DP-1", 2100, 200, 1280, 800),
+            &screens,
+            Some(&screens[0]),
+        );
+        assert_eq!(placed, Some(rect(320, 120, 1280, 800)));
+    }
+
+    #[test]
+    fn fully_off_screen_window_falls_back_to_the_first_monitor() {
+        let screens = desk();
+        let placed = place(&saved("HDMI-1", -5000, -5000, 1280, 800), &screens, None);
+        assert_eq!(placed, Some(rect(320, 120, 1280, 800)));
+    }
+
+    #[test]
+    fn partly_off_screen_window_slides_back_in() {
+        let screens = desk()[..1].to_vec();
+        // An unknown monitor name, but 120px still overlap the right edge;
+        // the bottom also runs under the taskbar.
+        let placed = place(&saved("HDMI-1", 1800, 600, 1280, 800), &screens, None);
+        assert_eq!(placed, Some(rect(640, 240, 1280, 800)));
+    }
+
+    #[test]
+    fn window_larger_than_its_monitor_is_shrunk_to_fit() {
+        let screens = desk();
+        let placed = place(&saved("eDP-1", -50, -50, 2560, 1440), &screens, None);
+        assert_eq!(placed, Some(rect(0, 0, 1920, 1040)));
+    }
+
+    #[test]
+    fn no_monitors_leaves_the_window_alone() {
+        assert_eq!(place(&saved("eDP-1", 0, 0, 1280, 800), &[], None), None);
+    }
+
+    #[test]
+    fn empty_geometry_is_ignored() {
+        assert_eq!(place(&saved("eDP-1", 0, 0, 0, 0), &desk(), None), None);
+    }
+}
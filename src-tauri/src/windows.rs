@@ -0,0 +1,121 @@
+//! Secondary windows that show one page on their own, e.g. the knowledge graph
+//! on a second monitor.
+//!
+//! Each [`WindowKind`] has a fixed label, so opening one that is already open
+//! just focuses it. They load the same frontend at the page's route; the
+//! frontend drops the sidebar when it isn't running in `main`. Cross-window
+//! selection goes over ordinary Tauri events (see `src/bus.ts`), which the
+//! `detached` capability allows. Closing the main window closes them too.
+
+use serde::Deserialize;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+use crate::logs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowKind {
+    Graph,
+    /// Documents; the library until there is a dedicated reader page.
+    Reader,
+    Quiz,
+}
+
+impl WindowKind {
+    const ALL: [WindowKind; 3] = [Self::Graph, Self::Reader, Self::Quiz];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Graph => "graph",
+            Self::Reader => "reader",
+            Self::Quiz => "quiz",
+        }
+    }
+
+    fn route(self) -> &'static str {
+        match self {
+            Self::Graph => "/graph",
+            Self::Reader => "/",
+            Self::Quiz => "/quiz",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Graph => "PageNode — Knowledge Graph",
+            Self::Reader => "PageNode — Library",
+            Self::Quiz => "PageNode — Review",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.label() == label)
+    }
+}
+
+/// Close every secondary window once the main one is gone.
+pub fn init(app: &AppHandle) {
+    let Some(main) = app.get_webview_window("main") else {
+        return;
+    };
+    let handle = app.clone();
+    main.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            for kind in WindowKind::ALL {
+                if let Some(window) = handle.get_webview_window(kind.label()) {
+                    let _ = window.close();
+                }
+            }
+        }
+    });
+}
+
+/// Open `kind` (or focus it if it's already open) and return its label.
+#[tauri::command]
+pub fn open_window(app: AppHandle, kind: WindowKind) -> Result<String, String> {
+    if let Some(window) = app.get_webview_window(kind.label()) {
+        let _ = window.unminimize();
+        window.show().map_err(|err| err.to_string())?;
+        window.set_focus().map_err(|err| err.to_string())?;
+        return Ok(kind.label().to_string());
+    }
+
+    WebviewWindowBuilder::new(
+        &app,
+        kind.label(),
+        WebviewUrl::App(format!("index.html#{}", kind.route()).into()),
+    )
+    .title(kind.title())
+    .inner_size(1000.0, 760.0)
+    .min_inner_size(640.0, 480.0)
+    .build()
+    .map_err(|err| {
+        logs::shell(
+            &app,
+            &format!("failed to open {} window: {err}", kind.label()),
+        );
+        err.to_string()
+    })?;
+    Ok(kind.label().to_string())
+}
+
+/// Bring any PageNode window (including `main`) to the front.
+#[tauri::command]
+pub fn focus_window(app: AppHandle, label: String) -> Result<(), String> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("no window labelled {label:?}"))?;
+    let _ = window.unminimize();
+    window.show().map_err(|err| err.to_string())?;
+    window.set_focus().map_err(|err| err.to_string())
+}
+
+/// Close a secondary window; `main` is closed by the user, not by command.
+#[tauri::command]
+pub fn close_window(app: AppHandle, label: String) -> Result<(), String> {
+    WindowKind::from_label(&label).ok_or_else(|| format!("cannot close {label:?}"))?;
+    match app.get_webview_window(&label) {
+        Some(window) => window.close().map_err(|err| err.to_string()),
+        None => Ok(()),
+    }
+}
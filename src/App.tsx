@@ -1,6 +1,16 @@
 import { useEffect, useState } from "react";
-import { Routes, Route } from "react-router-dom";
-import { apiFetch } from "./api";
+import { Routes, Route, useLocation, useNavigate } from "react-router-dom";
+import {
+  apiFetch,
+  getBackendState,
+  getSettings,
+  setLastRoute,
+  onBackendHealth,
+  onBackendStatus,
+  type BackendStatus,
+} from "./api";
+import { isDetached } from "./bus";
+import { onNativeEvent } from "./nativeEvents";
 import Sidebar from "./components/Sidebar";
 import SetupWizard from "./components/SetupWizard";
 import LibraryPage from "./pages/LibraryPage";
@@ -13,12 +23,60 @@ type HealthStatus = "loading" | "ok" | "error";
 export default function App() {
   const [health, setHealth] = useState<HealthStatus>("loading");
   const [setupComplete, setSetupComplete] = useState<boolean | null>(null);
+  const [backend, setBackend] = useState<BackendStatus | null>(null);
+  const navigate = useNavigate();
+  const location = useLocation();
+
+  // The shell routes us when the OS hands it a document
+  useEffect(
+    () => onNativeEvent<{ route: string }>("navigate", ({ route }) => navigate(route)),
+    [navigate],
+  );
+
+  // Menu bar actions that depend on where we are
+  useEffect(
+    () =>
+      onNativeEvent<{ action: "search" | "toggle-graph" }>("menu-action", ({ action }) => {
+        if (action === "toggle-graph") navigate(location.pathname === "/graph" ? "/" : "/graph");
+        else if (action === "search") navigate("/graph?search=1");
+      }),
+    [navigate, location.pathname],
+  );
 
   useEffect(() => {
+    if (!isDetached) setLastRoute(location.pathname).catch(() => {});
+  }, [location.pathname]);
+
+  useEffect(() => {
+    getBackendState().then(setBackend).catch(() => {});
+    const unlisten = onBackendStatus(setBackend);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = onBackendHealth((h) => setHealth(h.healthy ? "ok" : "error"));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (backend?.state !== "ready") return;
     apiFetch("/health")
       .then((r) => r.json())
       .then(() => setHealth("ok"))
       .catch(() => setHealth("error"));
+  }, [backend?.state]);
+
+  // A finished setup is on disk, so the app can open before the backend does
+  useEffect(() => {
+    getSettings()
+      .then((settings) => {
+        if (settings.setup_complete === "true") setSetupComplete((known) => known ?? true);
+      })
+      .catch(() => {});
   }, []);
 
   useEffect(() => {
@@ -29,8 +87,23 @@ export default function App() {
       .catch(() => setSetupComplete(true));
   }, [health]);
 
-  // Loading / backend error splash
-  if (health !== "ok" || setupComplete === null) {
+  // Sidecar never came up: show why instead of spinning forever
+  if (backend?.state === "failed") {
+    return (
+      <div style={s.splash}>
+        <div style={s.splashLogoMark}>P</div>
+        <span style={s.splashText}>Backend failed to start</span>
+        <span style={s.failReason}>{backend.reason}</span>
+        {backend.stderr_tail.length > 0 && (
+          <pre style={s.failLog}>{backend.stderr_tail.join("\n")}</pre>
+        )}
+      </div>
+    );
+  }
+
+  // Loading / backend error splash; a set-up library skips it and renders
+  // from the shell's copy of the database while the backend starts
+  if (setupComplete === null || (!setupComplete && health !== "ok")) {
     return (
       <div style={s.splash}>
         <div style={s.splashLogoMark}>P</div>
@@ -53,7 +126,7 @@ export default function App() {
   // Normal app: sidebar + main
   return (
     <div style={s.root}>
-      <Sidebar health={health} />
+      {!isDetached && <Sidebar health={health} />}
       <div style={s.main}>
         <Routes>
           <Route path="/" element={<LibraryPage health={health} />} />
@@ -112,6 +185,25 @@ const s: Record<string, React.CSSProperties> = {
     fontFamily: "'Crimson Pro', serif",
     fontWeight: 700,
   },
+  failReason: {
+    fontSize: "13px",
+    color: "#a63a3a",
+    fontFamily: "'Inter', sans-serif",
+  },
+  failLog: {
+    maxWidth: "720px",
+    maxHeight: "40vh",
+    overflow: "auto",
+    margin: 0,
+    padding: "12px 14px",
+    background: "#f3ede3",
+    border: "1px solid #e6dccb",
+    borderRadius: "6px",
+    fontSize: "11px",
+    color: "#5e5e5e",
+    fontFamily: "'JetBrains Mono', monospace",
+    whiteSpace: "pre-wrap",
+  },
   splashText: {
     fontSize: "12px",
     color: "#b0a08b",
@@ -1,23 +1,266 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
-let _baseUrl: string | null = null;
+// Every backend call goes through the Rust shell's `pagenode-api` scheme,
+// which waits for the sidecar, attaches the API token and forwards over
+// loopback. Windows webviews expose custom schemes as http://<scheme>.localhost.
+const BASE_URL = navigator.userAgent.includes("Windows")
+  ? "http://pagenode-api.localhost"
+  : "pagenode-api://localhost";
 
-export async function getBaseUrl(): Promise<string> {
-  if (_baseUrl) return _baseUrl;
+export type BackendStatus =
+  | { state: "starting" }
+  | { state: "ready"; port: number }
+  | { state: "restarting"; attempt: number; delay_ms: number }
+  | { state: "failed"; reason: string; stderr_tail: string[] }
+  | { state: "paused" }; // stopped for a backup or restore
+
+export function getBackendState(): Promise<BackendStatus> {
+  return invoke<BackendStatus>("get_backend_state");
+}
+
+// Emitted by the Rust sidecar supervisor on every state change.
+export function onBackendStatus(
+  cb: (status: BackendStatus) => void,
+): Promise<UnlistenFn> {
+  return listen<BackendStatus>("backend-status", (e) => cb(e.payload));
+}
+
+// Tail of ~/.pagenode/logs/backend.log, oldest line first.
+export function getRecentLogs(n: number): Promise<string[]> {
+  return invoke<string[]>("get_recent_logs", { n });
+}
+
+// Opens a native save dialog; resolves to the written path, or null if cancelled.
+export function exportDiagnostics(): Promise<string | null> {
+  return invoke<string | null>("export_diagnostics");
+}
+
+// Read by the shell straight from pagenode.db, so views can render while the
+// backend is still starting. Same shapes as GET /documents/ and GET /settings/;
+// they reject until the backend has created the database.
+export function listDocuments<T>(
+  offset = 0,
+  limit = 50,
+): Promise<{ items: T[]; total: number; offset: number; limit: number }> {
+  return invoke("list_documents", { offset, limit });
+}
+
+export function dueCardCount(): Promise<number> {
+  return invoke<number>("due_card_count");
+}
+
+export function getSettings(): Promise<Record<string, string>> {
+  return invoke<Record<string, string>>("get_settings");
+}
+
+export interface RestoreSummary {
+  created_at: string;
+  documents: number;
+  flashcards: number;
+}
+
+// Both open a native file dialog and resolve to null if it's cancelled. The
+// backend is stopped while they run and restarted afterwards.
+export function createBackup(): Promise<string | null> {
+  return invoke<string | null>("create_backup");
+}
+
+export function restoreBackup(): Promise<RestoreSummary | null> {
+  return invoke<RestoreSummary | null>("restore_backup");
+}
+
+export interface QuietHours {
+  start: string; // local "HH:MM"
+  end: string;
+}
+
+export interface NotificationPrefs {
+  documents: boolean;
+  due_cards: boolean;
+  quiet_hours: QuietHours | null;
+}
+
+export interface ShellPrefs {
+  keep_running_in_background: boolean;
+  notifications: NotificationPrefs;
+  capture_shortcut: string; // e.g. "CommandOrControl+Shift+Y"; "" disables
+}
+
+// Preferences owned by the Rust shell (~/.pagenode/shell.json), not the backend.
+export function getShellPrefs(): Promise<ShellPrefs> {
+  return invoke<ShellPrefs>("get_shell_prefs");
+}
+
+export function setShellPrefs(prefs: ShellPrefs): Promise<ShellPrefs> {
+  return invoke<ShellPrefs>("set_shell_prefs", { prefs });
+}
+
+// Fired when the prefs change from anywhere, including the tray menu.
+export function onShellPrefs(cb: (prefs: ShellPrefs) => void): Promise<UnlistenFn> {
+  return listen<ShellPrefs>("shell-prefs", (e) => cb(e.payload));
+}
+
+// Remembered in ~/.pagenode/window.json so the next launch reopens this page.
+export function setLastRoute(route: string): Promise<void> {
+  return invoke("set_last_route", { route });
+}
+
+// Back to the default size, centered, and forget the saved layout.
+export function resetWindowState(): Promise<void> {
+  return invoke("reset_window_state");
+}
+
+export type Grade = 0 | 1 | 2 | 3; // Again, Hard, Good, Easy
 
-  if (import.meta.env.DEV) {
-    _baseUrl = "/api";
-  } else {
-    const port = await invoke<number>("get_backend_port");
-    _baseUrl = `http://127.0.0.1:${port}`;
-  }
-  return _baseUrl;
+export interface ReviewResult {
+  id: string;
+  interval: number;
+  next_review: string;
+  repetitions: number;
+  difficulty: number;
+}
+
+// Days until the card is due again for each grade
+export interface IntervalPreview {
+  again: number;
+  hard: number;
+  good: number;
+  easy: number;
+}
+
+// Scheduled by the Rust shell straight into pagenode.db, so grading works
+// while the backend is down or restarting. The shell also queues the grade
+// for the backend's concept mastery and delivers it once the backend is up.
+export function reviewCard(cardId: string, grade: Grade): Promise<ReviewResult> {
+  return invoke<ReviewResult>("review_card", { cardId, grade });
+}
+
+export function previewIntervals(cardId: string): Promise<IntervalPreview> {
+  return invoke<IntervalPreview>("preview_intervals", { cardId });
+}
+
+export interface ReviewQueueStatus {
+  pending: number; // graded cards the backend hasn't applied to concept mastery yet
+}
+
+export function getReviewQueue(): Promise<ReviewQueueStatus> {
+  return invoke<ReviewQueueStatus>("get_review_queue");
+}
+
+// Fired as reviews are journaled and as they reach the backend.
+export function onReviewQueue(cb: (status: ReviewQueueStatus) => void): Promise<UnlistenFn> {
+  return listen<ReviewQueueStatus>("review-queue", (e) => cb(e.payload));
+}
+
+export type Scheduler = "sm2" | "fsrs";
+
+export interface SchedulerSettings {
+  scheduler: Scheduler;
+  optimized: boolean; // FSRS uses parameters fitted to this user's reviews
+  logged_reviews: number;
+}
+
+export interface FsrsFit {
+  parameters: number[];
+  reviews: number;
+  loss_before: number;
+  loss_after: number;
+}
+
+export function getScheduler(): Promise<SchedulerSettings> {
+  return invoke<SchedulerSettings>("get_scheduler");
+}
+
+export function setScheduler(scheduler: Scheduler): Promise<SchedulerSettings> {
+  return invoke<SchedulerSettings>("set_scheduler", { scheduler });
+}
+
+// Fits FSRS to the review log and switches to the fitted parameters.
+// Rejects with a message if there isn't enough history yet.
+export function optimizeFsrs(): Promise<FsrsFit> {
+  return invoke<FsrsFit>("optimize_fsrs");
+}
+
+export interface BackendHealth {
+  healthy: boolean;
+  latency_ms: number | null;
+  consecutive_failures: number;
+  error: string | null;
+}
+
+// Periodic /health probe results from the Rust shell.
+export function onBackendHealth(
+  cb: (health: BackendHealth) => void,
+): Promise<UnlistenFn> {
+  return listen<BackendHealth>("backend-health", (e) => cb(e.payload));
+}
+
+export async function getBaseUrl(): Promise<string> {
+  return BASE_URL;
 }
 
 export async function apiFetch(
   path: string,
   init?: RequestInit,
 ): Promise<Response> {
-  const base = await getBaseUrl();
-  return fetch(`${base}${path}`, init);
+  return fetch(`${BASE_URL}${path}`, init);
+}
+
+type StreamMessage =
+  | { kind: "head"; status: number; content_type: string | null }
+  | { kind: "chunk"; data: number[] }
+  | { kind: "end" };
+
+export interface EventStream {
+  done: Promise<void>; // resolves when the stream ends or is cancelled
+  cancel: () => void;
+}
+
+// Server-sent events from a backend GET, each `data:` payload parsed as JSON.
+// The pagenode-api scheme can only return whole responses, so the shell
+// relays these over an IPC channel as the chunks arrive.
+export function streamBackendEvents<T>(
+  path: string,
+  onEvent: (data: T) => void,
+): EventStream {
+  const channel = new Channel<StreamMessage>();
+  const decoder = new TextDecoder();
+  let buffer = "";
+  let cancelled = false;
+  let failed: string | null = null;
+
+  channel.onmessage = (message) => {
+    if (cancelled || failed) return;
+    if (message.kind === "head") {
+      if (message.status >= 400) failed = `${path} returned ${message.status}`;
+      return;
+    }
+    if (message.kind !== "chunk") return;
+    buffer += decoder.decode(new Uint8Array(message.data), { stream: true });
+    buffer = buffer.replace(/\r\n/g, "\n");
+    // An event ends at a blank line; its data lines join with newlines
+    let end: number;
+    while ((end = buffer.indexOf("\n\n")) !== -1) {
+      const data = buffer
+        .slice(0, end)
+        .split("\n")
+        .filter((line) => line.startsWith("data:"))
+        .map((line) => line.slice(5).replace(/^ /, ""))
+        .join("\n");
+      buffer = buffer.slice(end + 2);
+      if (data) onEvent(JSON.parse(data) as T);
+    }
+  };
+
+  const done = invoke<void>("stream_backend", { path, onEvent: channel }).then(() => {
+    if (failed) throw failed;
+  });
+  return {
+    done,
+    cancel: () => {
+      cancelled = true;
+      invoke("cancel_backend_stream", { id: channel.id }).catch(() => {});
+    },
+  };
 }
@@ -0,0 +1,42 @@
+import { invoke } from "@tauri-apps/api/core";
+import { emit, listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+
+// Selection sync between PageNode windows (main plus any popped-out page).
+// Every window receives every topic; a window never hears its own messages,
+// so reacting to a selection can't echo back and forth.
+
+export interface BusTopics {
+  "concept-selected": { id: string };
+  "document-selected": { id: string };
+}
+
+interface Envelope<T> {
+  source: string;
+  payload: T;
+}
+
+const self = getCurrentWindow().label;
+
+// Popped-out windows render a single page without the sidebar.
+export const isDetached = self !== "main";
+
+export function publish<K extends keyof BusTopics>(topic: K, payload: BusTopics[K]): Promise<void> {
+  return emit(`bus:${topic}`, { source: self, payload });
+}
+
+export function subscribe<K extends keyof BusTopics>(
+  topic: K,
+  cb: (payload: BusTopics[K]) => void,
+): Promise<UnlistenFn> {
+  return listen<Envelope<BusTopics[K]>>(`bus:${topic}`, (e) => {
+    if (e.payload.source !== self) cb(e.payload.payload);
+  });
+}
+
+export type WindowKind = "graph" | "reader" | "quiz";
+
+// Opens the page in its own window, or focuses it if already open.
+export function openWindow(kind: WindowKind): Promise<string> {
+  return invoke<string>("open_window", { kind });
+}
@@ -1,5 +1,5 @@
 import { useCallback, useEffect, useState } from "react";
-import { apiFetch } from "../api";
+import { apiFetch, streamBackendEvents } from "../api";
 import ModelCard from "./ModelCard";
 import ProgressBar from "./ProgressBar";
 
@@ -55,23 +55,15 @@ export default function SetupWizard({ onComplete }: SetupWizardProps) {
     fetchModels();
   }, [fetchModels]);
 
-  // Poll download progress when on step 2
+  // Follow download progress when on step 2
   useEffect(() => {
     if (step !== 2) return;
-    const poll = async () => {
-      try {
-        const res = await apiFetch("/settings/models/download/status");
-        const data: DownloadProgress = await res.json();
-        setProgress(data);
-        if (data.status === "complete" || data.status === "error" || data.status === "cancelled") {
-          return; // stop polling
-        }
-      } catch { /* ignore */ }
-      timerId = window.setTimeout(poll, 500);
-    };
-    let timerId: number;
-    poll();
-    return () => clearTimeout(timerId);
+    const stream = streamBackendEvents<DownloadProgress>(
+      "/settings/models/download/progress",
+      setProgress,
+    );
+    stream.done.catch(() => { /* ignore */ });
+    return stream.cancel;
   }, [step]);
 
   // Trigger embedding warm-up when entering step 3
@@ -1,4 +1,6 @@
+import { useEffect, useState } from "react";
 import { Link, useLocation } from "react-router-dom";
+import { dueCardCount, onBackendStatus, type BackendStatus } from "../api";
 
 interface SidebarProps {
   health: "loading" | "ok" | "error";
@@ -43,6 +45,20 @@ const SettingsIcon = () => (
 export default function Sidebar({ health }: SidebarProps) {
   const location = useLocation();
   const path = location.pathname;
+  const [backend, setBackend] = useState<BackendStatus | null>(null);
+  const [due, setDue] = useState<number | null>(null);
+
+  useEffect(() => {
+    const unlisten = onBackendStatus(setBackend);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Read from the database by the shell, so it shows before the backend is up
+  useEffect(() => {
+    dueCardCount().then(setDue).catch(() => {});
+  }, [path]);
 
   const navItems = [
     { to: "/", label: "Library", icon: <LibraryIcon /> },
@@ -51,10 +67,23 @@ export default function Sidebar({ health }: SidebarProps) {
     { to: "/settings", label: "Settings", icon: <SettingsIcon /> },
   ];
 
-  const healthColor =
-    health === "ok" ? "#3a8f5a" : health === "error" ? "#a63a3a" : "#b0a08b";
-  const healthLabel =
-    health === "loading" ? "connecting..." : health === "ok" ? "connected" : "unreachable";
+  const restarting =
+    backend?.state === "restarting" || backend?.state === "starting";
+  const failed = backend?.state === "failed";
+  const paused = backend?.state === "paused";
+
+  const healthColor = failed
+    ? "#a63a3a"
+    : restarting || paused
+      ? "#c08a2e"
+      : health === "ok" ? "#3a8f5a" : health === "error" ? "#a63a3a" : "#b0a08b";
+  const healthLabel = failed
+    ? "backend failed"
+    : paused
+      ? "paused for backup"
+      : restarting
+        ? "restarting..."
+      : health === "loading" ? "connecting..." : health === "ok" ? "connected" : "unreachable";
 
   return (
     <aside style={s.sidebar}>
@@ -87,6 +116,7 @@ export default function Sidebar({ health }: SidebarProps) {
               <span style={{ ...s.navLabel, color: active ? "#2b2b2b" : "#5e5e5e" }}>
                 {label}
               </span>
+              {to === "/quiz" && !!due && <span style={s.navCount}>{due}</span>}
             </Link>
           );
         })}
@@ -202,6 +232,12 @@ const s: Record<string, React.CSSProperties> = {
     fontWeight: 500,
     letterSpacing: "-0.1px",
   },
+  navCount: {
+    marginLeft: "auto",
+    fontSize: "11px",
+    fontFamily: "'JetBrains Mono', monospace",
+    color: "#a63a3a",
+  },
   statusCard: {
     background: "#f4f0e8",
     border: "1px solid rgba(0,0,0,0.08)",
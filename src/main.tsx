@@ -2,11 +2,17 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import { HashRouter } from "react-router-dom";
 import App from "./App";
+import CapturePage from "./pages/CapturePage";
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <HashRouter>
-      <App />
-    </HashRouter>
+    {/* The global-shortcut capture window loads the same bundle at #/capture */}
+    {window.location.hash.startsWith("#/capture") ? (
+      <CapturePage />
+    ) : (
+      <HashRouter>
+        <App />
+      </HashRouter>
+    )}
   </React.StrictMode>,
 );
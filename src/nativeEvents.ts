@@ -0,0 +1,53 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+
+// Events the Rust shell may fire before the page that handles them is
+// mounted (files opened from the OS arrive while we're still connecting).
+// The shell holds them until `ui_ready`; here they're held until someone
+// subscribes.
+const NATIVE_EVENTS = [
+  "navigate",
+  "menu-action",
+  "document-imported",
+  "import-failed",
+  "import-progress",
+] as const;
+
+export type NativeEvent = (typeof NATIVE_EVENTS)[number];
+
+type Handler = (payload: unknown) => void;
+
+const handlers = new Map<NativeEvent, Set<Handler>>();
+const pending = new Map<NativeEvent, unknown[]>();
+
+function dispatch(event: NativeEvent, payload: unknown) {
+  const subscribed = handlers.get(event);
+  if (subscribed && subscribed.size > 0) {
+    subscribed.forEach((cb) => cb(payload));
+  } else {
+    pending.set(event, [...(pending.get(event) ?? []), payload]);
+  }
+}
+
+// Only the main window drains the shell's queue
+if (getCurrentWindow().label === "main") {
+  Promise.all(NATIVE_EVENTS.map((event) => listen(event, (e) => dispatch(event, e.payload))))
+    .then(() => invoke<{ event: NativeEvent; payload: unknown }[]>("ui_ready"))
+    .then((queued) => queued.forEach(({ event, payload }) => dispatch(event, payload)))
+    .catch(() => {});
+}
+
+export function onNativeEvent<T>(event: NativeEvent, cb: (payload: T) => void): () => void {
+  const handler = cb as Handler;
+  if (!handlers.has(event)) handlers.set(event, new Set());
+  handlers.get(event)!.add(handler);
+
+  const backlog = pending.get(event) ?? [];
+  pending.delete(event);
+  backlog.forEach(handler);
+
+  return () => {
+    handlers.get(event)?.delete(handler);
+  };
+}
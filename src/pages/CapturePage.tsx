@@ -0,0 +1,224 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { apiFetch } from "../api";
+
+// Rendered alone in the always-on-top "capture" window opened by the global
+// shortcut. The shell hands over the grabbed selection; saving goes straight
+// to the backend.
+
+type Mode = "note" | "flashcard";
+
+interface DocumentOption {
+  id: string;
+  title: string;
+}
+
+export default function CapturePage() {
+  const [mode, setMode] = useState<Mode>("note");
+  const [text, setText] = useState("");
+  const [question, setQuestion] = useState("");
+  const [documents, setDocuments] = useState<DocumentOption[]>([]);
+  const [documentId, setDocumentId] = useState("");
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<string>("get_capture_text").then(setText).catch(() => {});
+    // Shortcut pressed again while we're open: take the new selection
+    const unlisten = listen<string>("capture-text", (e) => {
+      setText(e.payload);
+      setQuestion("");
+      setError(null);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    apiFetch("/documents/?limit=100")
+      .then((r) => r.json())
+      .then((data: { items: DocumentOption[] }) => setDocuments(data.items))
+      .catch(() => setError("Backend unreachable — is PageNode running?"));
+  }, []);
+
+  const close = () => getCurrentWindow().close();
+
+  useEffect(() => {
+    const onKey = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKey);
+    return () => window.removeEventListener("keydown", onKey);
+  }, []);
+
+  const canSave =
+    !saving &&
+    text.trim() !== "" &&
+    (mode === "note" || (question.trim() !== "" && documentId !== ""));
+
+  const handleSave = async () => {
+    if (!canSave) return;
+    setSaving(true);
+    setError(null);
+    const [path, body] =
+      mode === "note"
+        ? ["/notes/", { content: text.trim(), document_id: documentId || null }]
+        : ["/quiz/cards", { document_id: documentId, question: question.trim(), answer: text.trim() }];
+    try {
+      const res = await apiFetch(path, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(body),
+      });
+      if (!res.ok) {
+        const err = await res.json().catch(() => ({}));
+        throw new Error(err.detail || `Save failed (${res.status})`);
+      }
+      await close();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : "Save failed");
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div style={s.page}>
+      <div style={s.tabs}>
+        {(["note", "flashcard"] as const).map((m) => (
+          <button
+            key={m}
+            style={{ ...s.tab, ...(mode === m ? s.tabActive : {}) }}
+            onClick={() => setMode(m)}
+          >
+            {m === "note" ? "Note" : "Flashcard"}
+          </button>
+        ))}
+      </div>
+
+      {mode === "flashcard" && (
+        <>
+          <label style={s.label}>Question</label>
+          <input
+            style={s.input}
+            value={question}
+            onChange={(e) => setQuestion(e.target.value)}
+            placeholder="What should this card ask?"
+            autoFocus
+          />
+        </>
+      )}
+
+      <label style={s.label}>{mode === "note" ? "Note" : "Answer"}</label>
+      <textarea
+        style={{ ...s.input, flex: 1, minHeight: "120px", resize: "none" }}
+        value={text}
+        onChange={(e) => setText(e.target.value)}
+        placeholder="Nothing was selected — type or paste here"
+        autoFocus={mode === "note"}
+      />
+
+      <label style={s.label}>Document{mode === "note" ? " (optional)" : ""}</label>
+      <select style={s.input} value={documentId} onChange={(e) => setDocumentId(e.target.value)}>
+        <option value="">{mode === "note" ? "None" : "Choose a document…"}</option>
+        {documents.map((d) => (
+          <option key={d.id} value={d.id}>{d.title}</option>
+        ))}
+      </select>
+
+      {error && <div style={s.error}>{error}</div>}
+
+      <div style={s.actions}>
+        <button style={s.cancelBtn} onClick={close}>Cancel</button>
+        <button style={s.submitBtn} onClick={handleSave} disabled={!canSave}>
+          {saving ? "Saving…" : mode === "note" ? "Save note" : "Save card"}
+        </button>
+      </div>
+    </div>
+  );
+}
+
+const s: Record<string, React.CSSProperties> = {
+  page: {
+    height: "100vh",
+    display: "flex",
+    flexDirection: "column",
+    padding: "16px 20px",
+    background: "#fdfbf7",
+    boxSizing: "border-box",
+  },
+  tabs: {
+    display: "flex",
+    gap: "4px",
+  },
+  tab: {
+    padding: "5px 12px",
+    fontSize: "12px",
+    background: "none",
+    border: "1px solid rgba(0,0,0,0.12)",
+    borderRadius: "4px",
+    cursor: "pointer",
+    color: "#5e5e5e",
+    boxShadow: "none",
+  },
+  tabActive: {
+    background: "#2b2b2b",
+    color: "#fbf8f3",
+    borderColor: "#2b2b2b",
+  },
+  label: {
+    display: "block",
+    fontSize: "11px",
+    fontWeight: 600,
+    color: "#5e5e5e",
+    textTransform: "uppercase",
+    letterSpacing: "0.5px",
+    marginBottom: "4px",
+    marginTop: "12px",
+  },
+  input: {
+    width: "100%",
+    padding: "8px 10px",
+    fontSize: "13px",
+    border: "1px solid rgba(0,0,0,0.12)",
+    borderRadius: "4px",
+    background: "#fff",
+    fontFamily: "inherit",
+    boxSizing: "border-box",
+  },
+  error: {
+    marginTop: "10px",
+    fontSize: "12px",
+    color: "#a63a3a",
+  },
+  actions: {
+    display: "flex",
+    justifyContent: "flex-end",
+    gap: "8px",
+    marginTop: "16px",
+  },
+  cancelBtn: {
+    padding: "6px 14px",
+    fontSize: "12px",
+    background: "none",
+    border: "1px solid rgba(0,0,0,0.12)",
+    borderRadius: "4px",
+    cursor: "pointer",
+    color: "#5e5e5e",
+    boxShadow: "none",
+  },
+  submitBtn: {
+    padding: "6px 14px",
+    fontSize: "12px",
+    background: "#2b2b2b",
+    color: "#fbf8f3",
+    border: "none",
+    borderRadius: "4px",
+    cursor: "pointer",
+    fontWeight: 600,
+    boxShadow: "none",
+  },
+};
@@ -1,6 +1,8 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import cytoscape, { type Core, type EventObject } from "cytoscape";
+import { useLocation, useNavigate, useSearchParams } from "react-router-dom";
 import { apiFetch } from "../api";
+import { isDetached, openWindow, publish, subscribe } from "../bus";
 import ConceptModal from "../components/ConceptModal";
 import RelationshipModal from "../components/RelationshipModal";
 
@@ -58,6 +60,16 @@ export default function GraphPage({ health }: GraphPageProps) {
   const [searchQuery, setSearchQuery] = useState("");
   const [activeCategory, setActiveCategory] = useState<string | null>(null);
   const [hasExtracting, setHasExtracting] = useState(false);
+  const [graphVersion, setGraphVersion] = useState(0);
+  const [searchParams] = useSearchParams();
+  const focusConcept = searchParams.get("concept");
+  const focusSearch = searchParams.has("search");
+  const searchInputRef = useRef<HTMLInputElement>(null);
+  const [busConcept, setBusConcept] = useState<string | null>(null);
+  // Set while we select a node ourselves, so it isn't re-published
+  const silentTap = useRef(false);
+  const navigate = useNavigate();
+  const location = useLocation();
 
   const fetchGraph = useCallback(async () => {
     try {
@@ -109,6 +121,7 @@ export default function GraphPage({ health }: GraphPageProps) {
           gravity: 0.25,
         } as cytoscape.LayoutOptions).run();
       }
+      setGraphVersion((v) => v + 1);
 
       // Check if any documents are still in concept extraction phase
       try {
@@ -218,6 +231,7 @@ export default function GraphPage({ health }: GraphPageProps) {
     cy.on("tap", "node", async (evt: EventObject) => {
       const node = evt.target;
       const d = node.data();
+      if (!silentTap.current) publish("concept-selected", { id: d.id }).catch(() => {});
       try {
         const res = await apiFetch(`/graph/concepts/${d.id}`);
         const full = await res.json();
@@ -257,6 +271,45 @@ export default function GraphPage({ health }: GraphPageProps) {
     }
   }, [health, fetchGraph, fetchConceptList]);
 
+  // pagenode://concept/<id> lands here as ?concept=<id>; other windows
+  // select concepts over the bus
+  const conceptToFocus = busConcept ?? focusConcept;
+  useEffect(() => {
+    const cy = cyRef.current;
+    if (!conceptToFocus || !cy) return;
+    const node = cy.getElementById(conceptToFocus);
+    if (node.empty()) return;
+    cy.elements().unselect();
+    node.select();
+    silentTap.current = true;
+    node.emit("tap");
+    silentTap.current = false;
+    cy.animate({ center: { eles: node }, zoom: 1.5 }, { duration: 400 });
+  }, [conceptToFocus, graphVersion]);
+
+  // Search… from the menu bar
+  useEffect(() => {
+    if (focusSearch) searchInputRef.current?.select();
+  }, [focusSearch, location.key]);
+
+  useEffect(() => {
+    const offConcept = subscribe("concept-selected", ({ id }) => setBusConcept(id));
+    // A book picked in another window: highlight the concepts it produced
+    const offDocument = subscribe("document-selected", ({ id }) => {
+      const cy = cyRef.current;
+      if (!cy) return;
+      const nodes = cy.nodes().filter((n) => n.data("source_doc_id") === id);
+      cy.elements().unselect();
+      if (nodes.empty()) return;
+      nodes.select();
+      cy.animate({ fit: { eles: nodes, padding: 80 } }, { duration: 400 });
+    });
+    return () => {
+      offConcept.then((fn) => fn());
+      offDocument.then((fn) => fn());
+    };
+  }, []);
+
   // Auto-refresh when concept extraction is in progress
   useEffect(() => {
     if (!hasExtracting) return;
@@ -348,12 +401,22 @@ export default function GraphPage({ health }: GraphPageProps) {
         <button style={s.toolBtn} onClick={() => { fetchConceptList(); setRelModal(true); }}>+ Relationship</button>
         <button style={s.toolBtnSeed} onClick={handleSeed}>Seed Test Data</button>
         <button style={s.toolBtnSeed} onClick={() => { fetchGraph(); fetchConceptList(); }}>↻ Refresh</button>
+        {!isDetached && (
+          <button
+            style={s.toolBtnSeed}
+            onClick={() => openWindow("graph").then(() => navigate("/")).catch(() => {})}
+            title="Open the graph in its own window"
+          >
+            ⧉ Pop out
+          </button>
+        )}
         {hasExtracting && (
           <span style={s.extractingBadge}>● extracting concepts…</span>
         )}
 
         {/* Search */}
         <input
+          ref={searchInputRef}
           style={s.searchInput}
           type="text"
           placeholder="Search concepts..."
@@ -1,6 +1,9 @@
 import { useCallback, useEffect, useRef, useState } from "react";
-import { useNavigate } from "react-router-dom";
-import { apiFetch } from "../api";
+import { useNavigate, useSearchParams } from "react-router-dom";
+import { getCurrentWebview } from "@tauri-apps/api/webview";
+import { apiFetch, listDocuments } from "../api";
+import { publish } from "../bus";
+import { onNativeEvent } from "../nativeEvents";
 
 interface DocumentItem {
   id: string;
@@ -25,6 +28,35 @@ interface LibraryPageProps {
 
 type UploadStep = 0 | 1 | 2 | 3; // 0=closed, 1=select, 2=processing, 3=review
 
+// Files dropped on the window are hashed and handed to the backend by the shell
+type ImportStage =
+  | "queued"
+  | "hashing"
+  | "ingesting"
+  | "done"
+  | "duplicate"
+  | "unsupported"
+  | "failed";
+
+interface ImportProgress {
+  path: string;
+  index: number;
+  total: number;
+  stage: ImportStage;
+}
+
+const IMPORT_STAGE_LABELS: Record<ImportStage, string> = {
+  queued: "waiting",
+  hashing: "checking",
+  ingesting: "adding",
+  done: "added",
+  duplicate: "already in library",
+  unsupported: "unsupported type",
+  failed: "failed",
+};
+
+const IMPORT_FINISHED = new Set<ImportStage>(["done", "duplicate", "unsupported", "failed"]);
+
 const BOOK_COLORS = [
   "#333333", "#a63a3a", "#3a5fa6", "#3a8f5a",
   "#634e3b", "#1e2b42", "#7b5e3a", "#4a3a6a",
@@ -84,7 +116,12 @@ export default function LibraryPage({ health }: LibraryPageProps) {
   const [processingDocId, setProcessingDocId] = useState<string | null>(null);
   const [processingDoc, setProcessingDoc] = useState<DocumentItem | null>(null);
   const [uploadError, setUploadError] = useState<string | null>(null);
+  const [importBatch, setImportBatch] = useState<ImportProgress[]>([]);
   const fileInputRef = useRef<HTMLInputElement>(null);
+  // pagenode://document/<id> lands here as ?document=<id>; there is no reader
+  // yet, so `page` rides along in the route unused
+  const [searchParams] = useSearchParams();
+  const focusDocument = searchParams.get("document");
 
   const fetchDocs = useCallback(async () => {
     try {
@@ -94,10 +131,72 @@ export default function LibraryPage({ health }: LibraryPageProps) {
     } catch { /* ignore */ }
   }, []);
 
+  // The shelf as last saved, before the backend is up
+  useEffect(() => {
+    listDocuments<DocumentItem>(0, 100)
+      .then((data) => setDocuments((current) => (current.length ? current : data.items)))
+      .catch(() => {});
+  }, []);
+
   useEffect(() => {
     if (health === "ok") fetchDocs();
   }, [health, fetchDocs]);
 
+  // Files opened from the OS are uploaded by the shell; pick up at processing
+  useEffect(() => {
+    const offImported = onNativeEvent<{ path: string; document: DocumentItem }>(
+      "document-imported",
+      ({ document }) => {
+        setUploadError(null);
+        setProcessingDocId(document.id);
+        setProcessingDoc(document);
+        setUploadStep(2);
+        fetchDocs();
+      },
+    );
+    const offFailed = onNativeEvent<{ path: string; error: string }>(
+      "import-failed",
+      ({ path, error }) => {
+        const name = path.split(/[\\/]/).pop();
+        setProcessingDoc(null);
+        setProcessingDocId(null);
+        setUploadError(`${name}: ${error}`);
+        setUploadStep(1);
+      },
+    );
+    const offProgress = onNativeEvent<ImportProgress>("import-progress", (progress) => {
+      setImportBatch((batch) => {
+        // A new batch starts once the previous one has finished
+        const fresh = batch.length !== progress.total || batch.every((p) => IMPORT_FINISHED.has(p.stage));
+        const next = fresh && progress.stage === "queued" && progress.index === 0 ? [] : [...batch];
+        next[progress.index] = progress;
+        return next;
+      });
+    });
+    return () => {
+      offImported();
+      offFailed();
+      offProgress();
+    };
+  }, [fetchDocs]);
+
+  // Clear the multi-file list a little after the last file is through
+  useEffect(() => {
+    if (importBatch.length === 0 || !importBatch.every((p) => p && IMPORT_FINISHED.has(p.stage))) return;
+    const timer = setTimeout(() => setImportBatch([]), 4000);
+    return () => clearTimeout(timer);
+  }, [importBatch]);
+
+  // The shell receives dropped files; the webview only hears about the hover
+  useEffect(() => {
+    const unlisten = getCurrentWebview().onDragDropEvent((e) => {
+      setDragOver(e.payload.type === "enter" || e.payload.type === "over");
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Poll all docs while any are processing
   useEffect(() => {
     const hasProcessing = documents.some((d) => STATUS_PROCESSING.has(d.status));
@@ -211,6 +310,7 @@ export default function LibraryPage({ health }: LibraryPageProps) {
                   color={BOOK_COLORS[i % BOOK_COLORS.length]}
                   onDelete={(e) => handleDelete(doc.id, e)}
                   onViewGraph={() => navigate("/graph")}
+                  focused={doc.id === focusDocument}
                 />
               ))}
               <div style={s.addSpine} onClick={openUploadModal} title="Add a book">
@@ -223,16 +323,38 @@ export default function LibraryPage({ health }: LibraryPageProps) {
         )}
       </div>
 
+      {/* Drop target while files are dragged over the window */}
+      {dragOver && uploadStep === 0 && (
+        <div style={s.dropOverlay}>
+          <span style={s.dropOverlayText}>Drop to add to your library</span>
+        </div>
+      )}
+
+      {/* Multi-file drop progress */}
+      {importBatch.length > 1 && (
+        <div style={s.importPanel}>
+          {importBatch.map((p) => p && (
+            <div key={p.index} style={s.importRow}>
+              <span style={s.importName}>{p.path.split(/[\\/]/).pop()}</span>
+              <span style={{
+                ...s.importStage,
+                color: p.stage === "failed" || p.stage === "unsupported" ? "#a63a3a" : p.stage === "done" ? "#3a8f5a" : "#5e5e5e",
+              }}>
+                {IMPORT_STAGE_LABELS[p.stage]}
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Upload modal */}
       {uploadStep > 0 && (
         <UploadModal
           step={uploadStep}
           dragOver={dragOver}
-          setDragOver={setDragOver}
           processingDoc={processingDoc}
           uploadError={uploadError}
           fileInputRef={fileInputRef}
-          onFileDrop={handleFileDrop}
           onClose={closeModal}
           onViewGraph={() => { closeModal(); navigate("/graph"); }}
         />
@@ -260,25 +382,34 @@ interface BookSpineProps {
   color: string;
   onDelete: (e: React.MouseEvent) => void;
   onViewGraph: () => void;
+  focused?: boolean;
 }
 
-function BookSpine({ doc, color, onDelete, onViewGraph }: BookSpineProps) {
+function BookSpine({ doc, color, onDelete, onViewGraph, focused = false }: BookSpineProps) {
   const [hovered, setHovered] = useState(false);
+  const spineRef = useRef<HTMLDivElement>(null);
+  const lifted = hovered || focused;
+
+  useEffect(() => {
+    if (focused) spineRef.current?.scrollIntoView({ behavior: "smooth", inline: "center" });
+  }, [focused]);
   const isProcessing = STATUS_PROCESSING.has(doc.status);
   const statusColor = STATUS_COLORS[doc.status] || "#999";
 
   return (
     <div
+      ref={spineRef}
       style={{
         ...bs.spine,
         background: `linear-gradient(105deg, ${color}cc 0%, ${color} 40%, ${color}ee 100%)`,
-        transform: hovered ? "translateY(-14px)" : "translateY(0)",
-        boxShadow: hovered
+        transform: lifted ? "translateY(-14px)" : "translateY(0)",
+        boxShadow: lifted
           ? "4px 0 16px rgba(0,0,0,0.4), inset 2px 0 0 rgba(255,255,255,0.18)"
           : "2px 0 6px rgba(0,0,0,0.22), inset 2px 0 0 rgba(255,255,255,0.1)",
       }}
       onMouseEnter={() => setHovered(true)}
       onMouseLeave={() => setHovered(false)}
+      onClick={() => publish("document-selected", { id: doc.id }).catch(() => {})}
       title={doc.title}
     >
       <span style={bs.title}>{doc.title}</span>
@@ -401,18 +532,16 @@ const bs: Record<string, React.CSSProperties> = {
 interface UploadModalProps {
   step: UploadStep;
   dragOver: boolean;
-  setDragOver: (v: boolean) => void;
   processingDoc: DocumentItem | null;
   uploadError: string | null;
   fileInputRef: React.RefObject<HTMLInputElement | null>;
-  onFileDrop: (file: File) => void;
   onClose: () => void;
   onViewGraph: () => void;
 }
 
 function UploadModal({
-  step, dragOver, setDragOver, processingDoc, uploadError,
-  fileInputRef, onFileDrop, onClose, onViewGraph,
+  step, dragOver, processingDoc, uploadError,
+  fileInputRef, onClose, onViewGraph,
 }: UploadModalProps) {
   return (
     <div style={m.backdrop} onClick={onClose}>
@@ -453,14 +582,6 @@ function UploadModal({
                 borderColor: dragOver ? "#2b2b2b" : "rgba(0,0,0,0.15)",
                 background: dragOver ? "rgba(43,43,43,0.03)" : "transparent",
               }}
-              onDrop={(e) => {
-                e.preventDefault();
-                setDragOver(false);
-                const file = e.dataTransfer.files[0];
-                if (file) onFileDrop(file);
-              }}
-              onDragOver={(e) => { e.preventDefault(); setDragOver(true); }}
-              onDragLeave={() => setDragOver(false)}
             >
               <div style={m.pdfIconWrap}>
                 <svg width="28" height="36" viewBox="0 0 28 36" fill="none">
@@ -592,6 +713,7 @@ const s: Record<string, React.CSSProperties> = {
     flexDirection: "column",
     overflow: "hidden",
     background: "#fbf8f3",
+    position: "relative",
   },
   header: {
     display: "flex",
@@ -719,6 +841,55 @@ const s: Record<string, React.CSSProperties> = {
     borderRadius: "6px",
     cursor: "pointer",
   },
+  dropOverlay: {
+    position: "absolute",
+    inset: "16px",
+    zIndex: 50,
+    display: "flex",
+    alignItems: "center",
+    justifyContent: "center",
+    border: "2px dashed #2b2b2b",
+    borderRadius: "10px",
+    background: "rgba(251,248,243,0.85)",
+    pointerEvents: "none",
+  },
+  dropOverlayText: {
+    fontSize: "14px",
+    fontWeight: 600,
+    color: "#2b2b2b",
+  },
+  importPanel: {
+    position: "absolute",
+    right: "24px",
+    bottom: "24px",
+    zIndex: 40,
+    width: "300px",
+    maxHeight: "240px",
+    overflowY: "auto",
+    padding: "10px 14px",
+    background: "#fdfbf7",
+    border: "1px solid rgba(0,0,0,0.1)",
+    borderRadius: "8px",
+    boxShadow: "0 4px 16px rgba(0,0,0,0.08)",
+  },
+  importRow: {
+    display: "flex",
+    justifyContent: "space-between",
+    gap: "12px",
+    padding: "4px 0",
+    fontSize: "12px",
+  },
+  importName: {
+    color: "#2b2b2b",
+    overflow: "hidden",
+    textOverflow: "ellipsis",
+    whiteSpace: "nowrap",
+  },
+  importStage: {
+    flexShrink: 0,
+    fontFamily: "'JetBrains Mono', monospace",
+    fontSize: "11px",
+  },
 };
 
 // ─── Modal styles ─────────────────────────────────────────────────────────────
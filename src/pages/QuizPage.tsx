@@ -1,5 +1,14 @@
 import { useCallback, useEffect, useState } from "react";
-import { apiFetch } from "../api";
+import { useLocation, useSearchParams } from "react-router-dom";
+import {
+  apiFetch,
+  getReviewQueue,
+  onReviewQueue,
+  previewIntervals,
+  reviewCard,
+  type Grade,
+  type IntervalPreview,
+} from "../api";
 
 interface QuizPageProps {
   health: "loading" | "ok" | "error";
@@ -31,6 +40,7 @@ interface QuizStats {
 }
 
 const GRADE_LABELS = ["Again", "Hard", "Good", "Easy"] as const;
+const GRADE_KEYS = ["again", "hard", "good", "easy"] as const;
 const GRADE_COLORS = ["#c0392b", "#d35400", "#27ae60", "#2980b9"] as const;
 const GRADE_BG = [
   "rgba(192,57,43,0.08)",
@@ -39,8 +49,16 @@ const GRADE_BG = [
   "rgba(41,128,185,0.08)",
 ] as const;
 
+function formatInterval(days: number): string {
+  if (days < 30) return `${days}d`;
+  if (days < 365) return `${Math.round(days / 30)}mo`;
+  return `${(days / 365).toFixed(1)}y`;
+}
+
 export default function QuizPage({ health }: QuizPageProps) {
   const [tab, setTab] = useState<"quiz" | "dashboard">("quiz");
+  const [searchParams] = useSearchParams();
+  const location = useLocation();
 
   // --- Quiz tab state ---
   const [cards, setCards] = useState<Flashcard[]>([]);
@@ -51,6 +69,8 @@ export default function QuizPage({ health }: QuizPageProps) {
   const [sessionDone, setSessionDone] = useState(false);
   const [quizLoading, setQuizLoading] = useState(false);
   const [quizError, setQuizError] = useState<string | null>(null);
+  const [intervals, setIntervals] = useState<IntervalPreview | null>(null);
+  const [pendingSync, setPendingSync] = useState(0);
 
   // --- Dashboard tab state ---
   const [stats, setStats] = useState<QuizStats | null>(null);
@@ -101,22 +121,42 @@ export default function QuizPage({ health }: QuizPageProps) {
     }
   }, [tab, health]); // eslint-disable-line react-hooks/exhaustive-deps
 
+  // pagenode://quiz/due: back to the quiz tab, with a fresh batch if finished
+  useEffect(() => {
+    if (searchParams.get("session") !== "due") return;
+    setTab("quiz");
+    if (sessionDone && health === "ok") loadDueCards();
+  }, [location.key]); // eslint-disable-line react-hooks/exhaustive-deps
+
+  useEffect(() => {
+    getReviewQueue().then((q) => setPendingSync(q.pending)).catch(() => {});
+    const unlisten = onReviewQueue((q) => setPendingSync(q.pending));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const handleFlip = () => {
     if (!flipped) setFlipped(true);
   };
 
-  const handleGrade = async (grade: 0 | 1 | 2 | 3) => {
+  const handleGrade = async (grade: Grade) => {
     const card = cards[current];
     if (!card) return;
 
     try {
-      await apiFetch(`/quiz/${card.id}/review`, {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({ grade }),
-      });
+      await reviewCard(card.id, grade);
     } catch {
-      // best-effort; advance anyway
+      // No local database yet (first run): let the backend schedule it
+      try {
+        await apiFetch(`/quiz/${card.id}/review`, {
+          method: "POST",
+          headers: { "Content-Type": "application/json" },
+          body: JSON.stringify({ grade }),
+        });
+      } catch {
+        // best-effort; advance anyway
+      }
     }
 
     const isCorrect = grade >= 2;
@@ -139,6 +179,13 @@ export default function QuizPage({ health }: QuizPageProps) {
 
   const card = cards[current] ?? null;
 
+  // Button labels ("Good 4d") come from the shell's scheduler
+  useEffect(() => {
+    setIntervals(null);
+    if (!card) return;
+    previewIntervals(card.id).then(setIntervals).catch(() => {});
+  }, [card?.id]); // eslint-disable-line react-hooks/exhaustive-deps
+
   // ---- Render ----
 
   const renderTabBar = () => (
@@ -156,7 +203,8 @@ export default function QuizPage({ health }: QuizPageProps) {
   );
 
   const renderQuizTab = () => {
-    if (health !== "ok") {
+    // A session already loaded carries on: grading doesn't need the backend
+    if (health !== "ok" && cards.length === 0) {
       return <div style={s.emptyMsg}>Backend not connected.</div>;
     }
     if (quizLoading) {
@@ -276,17 +324,11 @@ export default function QuizPage({ health }: QuizPageProps) {
                 onClick={() => handleGrade(g)}
               >
                 {GRADE_LABELS[g]}
+                {intervals && <span style={s.gradeInterval}>{formatInterval(intervals[GRADE_KEYS[g]])}</span>}
               </button>
             ))}
           </div>
         )}
-
-        {/* Interval hint */}
-        {flipped && (
-          <div style={s.intervalHint}>
-            Again → tomorrow &nbsp;·&nbsp; Hard → {card.interval}d &nbsp;·&nbsp; Good → {Math.max(1, Math.round(card.interval * (2.5 - card.difficulty * 1.2)))}d &nbsp;·&nbsp; Easy → {Math.max(1, Math.round(card.interval * (2.5 - card.difficulty * 1.2) * 1.3))}d
-          </div>
-        )}
       </div>
     );
   };
@@ -370,7 +412,14 @@ export default function QuizPage({ health }: QuizPageProps) {
     <div style={s.page}>
       <div style={s.header}>
         <div style={s.headerTitle}>Quiz</div>
-        <div style={s.headerSub}>Spaced repetition review</div>
+        <div style={s.headerSub}>
+          Spaced repetition review
+          {pendingSync > 0 && (
+            <span style={s.pendingSync}>
+              {" "}· {pendingSync} review{pendingSync === 1 ? "" : "s"} waiting to sync
+            </span>
+          )}
+        </div>
       </div>
       {renderTabBar()}
       <div style={s.content}>
@@ -407,6 +456,9 @@ const s: Record<string, React.CSSProperties> = {
     marginTop: "3px",
     letterSpacing: "0.2px",
   },
+  pendingSync: {
+    color: "#a06a2c",
+  },
   tabBar: {
     display: "flex",
     gap: "4px",
@@ -567,12 +619,12 @@ const s: Record<string, React.CSSProperties> = {
     transition: "opacity 0.1s",
     minWidth: "72px",
   },
-  intervalHint: {
-    textAlign: "center" as React.CSSProperties["textAlign"],
+  gradeInterval: {
+    marginLeft: "6px",
     fontSize: "10px",
-    color: "#b0a08b",
+    fontWeight: 400,
+    opacity: 0.75,
     fontFamily: "'JetBrains Mono', monospace",
-    letterSpacing: "0.2px",
   },
   // --- Summary / empty states ---
   emptyMsg: {
@@ -1,5 +1,23 @@
 import { useCallback, useEffect, useState } from "react";
-import { apiFetch } from "../api";
+import {
+  apiFetch,
+  createBackup,
+  exportDiagnostics,
+  getScheduler,
+  getShellPrefs,
+  onShellPrefs,
+  optimizeFsrs,
+  resetWindowState,
+  restoreBackup,
+  setScheduler,
+  setShellPrefs,
+  streamBackendEvents,
+  type FsrsFit,
+  type NotificationPrefs,
+  type Scheduler,
+  type SchedulerSettings,
+  type ShellPrefs,
+} from "../api";
 import ModelCard from "../components/ModelCard";
 import ProgressBar from "../components/ProgressBar";
 
@@ -41,6 +59,14 @@ export default function SettingsPage({ health }: SettingsPageProps) {
   const [progress, setProgress] = useState<DownloadProgress | null>(null);
   const [downloading, setDownloading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [diagnosticsPath, setDiagnosticsPath] = useState<string | null>(null);
+  const [exporting, setExporting] = useState(false);
+  const [shellPrefs, setShellPrefsState] = useState<ShellPrefs | null>(null);
+  const [scheduling, setScheduling] = useState<SchedulerSettings | null>(null);
+  const [optimizing, setOptimizing] = useState(false);
+  const [fit, setFit] = useState<FsrsFit | null>(null);
+  const [backupBusy, setBackupBusy] = useState(false);
+  const [backupPath, setBackupPath] = useState<string | null>(null);
 
   const fetchData = useCallback(async () => {
     try {
@@ -57,29 +83,38 @@ export default function SettingsPage({ health }: SettingsPageProps) {
     if (health === "ok") fetchData();
   }, [health, fetchData]);
 
-  // Poll download progress while downloading
+  useEffect(() => {
+    getScheduler().then(setScheduling).catch(() => {});
+  }, []);
+
+  useEffect(() => {
+    getShellPrefs().then(setShellPrefsState).catch(() => {});
+    const unlisten = onShellPrefs(setShellPrefsState);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow download progress while downloading
   useEffect(() => {
     if (!downloading) return;
-    const poll = async () => {
-      try {
-        const res = await apiFetch("/settings/models/download/status");
-        const data: DownloadProgress = await res.json();
+    const stream = streamBackendEvents<DownloadProgress>(
+      "/settings/models/download/progress",
+      (data) => {
         setProgress(data);
         if (data.status === "complete") {
           setDownloading(false);
           fetchData(); // refresh installed sizes
-          return;
-        }
-        if (data.status === "error" || data.status === "cancelled") {
+        } else if (data.status === "error" || data.status === "cancelled") {
           setDownloading(false);
-          return;
         }
-      } catch { /* ignore */ }
-      timerId = window.setTimeout(poll, 500);
-    };
-    let timerId: number;
-    poll();
-    return () => clearTimeout(timerId);
+      },
+    );
+    stream.done.catch((e) => {
+      setError(typeof e === "string" ? e : "Lost track of the download");
+      setDownloading(false);
+    });
+    return stream.cancel;
   }, [downloading, fetchData]);
 
   const handleDownload = async (modelId: string) => {
@@ -126,6 +161,81 @@ export default function SettingsPage({ health }: SettingsPageProps) {
     }
   };
 
+  const handleExportDiagnostics = async () => {
+    setError(null);
+    setExporting(true);
+    try {
+      const path = await exportDiagnostics();
+      if (path) setDiagnosticsPath(path);
+    } catch (e) {
+      setError(typeof e === "string" ? e : "Failed to export diagnostics");
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const updateShellPrefs = async (change: Partial<ShellPrefs>) => {
+    if (!shellPrefs) return;
+    try {
+      setShellPrefsState(await setShellPrefs({ ...shellPrefs, ...change }));
+    } catch (e) {
+      setError(typeof e === "string" ? e : "Failed to save preferences");
+    }
+  };
+
+  const updateNotifications = (change: Partial<NotificationPrefs>) => {
+    if (!shellPrefs) return;
+    updateShellPrefs({ notifications: { ...shellPrefs.notifications, ...change } });
+  };
+
+  const handleScheduler = async (scheduler: Scheduler) => {
+    setError(null);
+    try {
+      setScheduling(await setScheduler(scheduler));
+    } catch (e) {
+      setError(typeof e === "string" ? e : "Failed to change scheduler");
+    }
+  };
+
+  const handleOptimize = async () => {
+    setError(null);
+    setOptimizing(true);
+    try {
+      setFit(await optimizeFsrs());
+      setScheduling(await getScheduler());
+    } catch (e) {
+      setError(typeof e === "string" ? e : "Optimization failed");
+    } finally {
+      setOptimizing(false);
+    }
+  };
+
+  const handleBackup = async () => {
+    setError(null);
+    setBackupBusy(true);
+    try {
+      const path = await createBackup();
+      if (path) setBackupPath(path);
+    } catch (e) {
+      setError(typeof e === "string" ? e : "Backup failed");
+    } finally {
+      setBackupBusy(false);
+    }
+  };
+
+  const handleRestore = async () => {
+    setError(null);
+    setBackupBusy(true);
+    try {
+      // Everything on screen belongs to the old library; start over
+      if (await restoreBackup()) window.location.reload();
+    } catch (e) {
+      setError(typeof e === "string" ? e : "Restore failed");
+    } finally {
+      setBackupBusy(false);
+    }
+  };
+
   const formatBytes = (bytes: number): string => {
     if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(0)} KB`;
     if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
@@ -220,6 +330,205 @@ export default function SettingsPage({ health }: SettingsPageProps) {
           ))}
         </div>
       </section>
+
+      {/* Review scheduling section */}
+      <section style={s.section}>
+        <h2 style={s.sectionTitle}>Review Scheduling</h2>
+        <p style={s.sectionDesc}>
+          SM-2 is the classic spaced-repetition algorithm. FSRS models how well you
+          remember each card and usually needs fewer reviews for the same recall; it
+          can be tuned to your own review history once you have a few weeks of it.
+          Switching keeps every card's progress.
+        </p>
+        {scheduling && (
+          <>
+            <label style={s.checkRow}>
+              <input
+                type="radio"
+                name="scheduler"
+                checked={scheduling.scheduler === "sm2"}
+                onChange={() => handleScheduler("sm2")}
+              />
+              SM-2
+            </label>
+            <label style={s.checkRow}>
+              <input
+                type="radio"
+                name="scheduler"
+                checked={scheduling.scheduler === "fsrs"}
+                onChange={() => handleScheduler("fsrs")}
+              />
+              FSRS{scheduling.optimized ? " (optimized for you)" : ""}
+            </label>
+            <button
+              style={{ ...s.ghostBtn, marginTop: "8px" }}
+              onClick={handleOptimize}
+              disabled={optimizing}
+            >
+              {optimizing ? "Optimizing…" : "Optimize FSRS from my reviews"}
+            </button>
+            <p style={s.savedPath}>
+              {fit
+                ? `Fitted to ${fit.reviews} reviews; prediction loss ${fit.loss_before.toFixed(3)} → ${fit.loss_after.toFixed(3)}.`
+                : `${scheduling.logged_reviews} reviews logged so far.`}
+            </p>
+          </>
+        )}
+      </section>
+
+      {/* Window section */}
+      <section style={s.section}>
+        <h2 style={s.sectionTitle}>Window</h2>
+        <p style={s.sectionDesc}>
+          Keep PageNode in the system tray when the window is closed, so the due-card
+          count stays visible and the backend doesn't have to start again. The window's
+          size, position and last page are restored on the next launch.
+        </p>
+        <label style={s.checkRow}>
+          <input
+            type="checkbox"
+            checked={shellPrefs?.keep_running_in_background ?? false}
+            disabled={!shellPrefs}
+            onChange={(e) => updateShellPrefs({ keep_running_in_background: e.target.checked })}
+          />
+          Keep running when the window is closed
+        </label>
+        <button
+          style={{ ...s.ghostBtn, marginTop: "8px" }}
+          onClick={() => resetWindowState().catch(() => {})}
+        >
+          Reset window size and position
+        </button>
+      </section>
+
+      {/* Capture section */}
+      <section style={s.section}>
+        <h2 style={s.sectionTitle}>Quick Capture</h2>
+        <p style={s.sectionDesc}>
+          A global shortcut that grabs the current selection (or clipboard) from any app
+          and saves it as a note or flashcard. Leave empty to turn it off.
+        </p>
+        {shellPrefs && (
+          <input
+            key={shellPrefs.capture_shortcut}
+            style={s.shortcutInput}
+            defaultValue={shellPrefs.capture_shortcut}
+            placeholder="CommandOrControl+Shift+Y"
+            onBlur={(e) => {
+              if (e.target.value.trim() !== shellPrefs.capture_shortcut) {
+                updateShellPrefs({ capture_shortcut: e.target.value.trim() });
+              }
+            }}
+          />
+        )}
+      </section>
+
+      {/* Notifications section */}
+      <section style={s.section}>
+        <h2 style={s.sectionTitle}>Notifications</h2>
+        <p style={s.sectionDesc}>
+          Desktop notifications while PageNode is in the background.
+        </p>
+        {shellPrefs && (
+          <>
+            <label style={s.checkRow}>
+              <input
+                type="checkbox"
+                checked={shellPrefs.notifications.documents}
+                onChange={(e) => updateNotifications({ documents: e.target.checked })}
+              />
+              When a document finishes processing
+            </label>
+            <label style={s.checkRow}>
+              <input
+                type="checkbox"
+                checked={shellPrefs.notifications.due_cards}
+                onChange={(e) => updateNotifications({ due_cards: e.target.checked })}
+              />
+              When flashcards are due (once a day)
+            </label>
+            <label style={s.checkRow}>
+              <input
+                type="checkbox"
+                checked={shellPrefs.notifications.quiet_hours !== null}
+                onChange={(e) =>
+                  updateNotifications({
+                    quiet_hours: e.target.checked ? { start: "22:00", end: "08:00" } : null,
+                  })
+                }
+              />
+              Quiet hours
+              {shellPrefs.notifications.quiet_hours && (
+                <>
+                  <input
+                    type="time"
+                    style={s.timeInput}
+                    value={shellPrefs.notifications.quiet_hours.start}
+                    onChange={(e) =>
+                      e.target.value &&
+                      updateNotifications({
+                        quiet_hours: { ...shellPrefs.notifications.quiet_hours!, start: e.target.value },
+                      })
+                    }
+                  />
+                  to
+                  <input
+                    type="time"
+                    style={s.timeInput}
+                    value={shellPrefs.notifications.quiet_hours.end}
+                    onChange={(e) =>
+                      e.target.value &&
+                      updateNotifications({
+                        quiet_hours: { ...shellPrefs.notifications.quiet_hours!, end: e.target.value },
+                      })
+                    }
+                  />
+                </>
+              )}
+            </label>
+          </>
+        )}
+      </section>
+
+      {/* Backup section */}
+      <section style={s.section}>
+        <h2 style={s.sectionTitle}>Backup</h2>
+        <p style={s.sectionDesc}>
+          Save your whole library — documents, notes, flashcards, the knowledge graph
+          and search index — as a single archive, or replace the library with one.
+          The backend pauses while this runs. Downloaded models aren't included.
+        </p>
+        <div style={{ display: "flex", gap: "8px" }}>
+          <button style={s.primaryBtn} onClick={handleBackup} disabled={backupBusy}>
+            {backupBusy ? "Working…" : "Back up library…"}
+          </button>
+          <button style={s.ghostBtn} onClick={handleRestore} disabled={backupBusy}>
+            Restore from backup…
+          </button>
+        </div>
+        {backupPath && (
+          <p style={s.savedPath}>
+            Saved to <code style={s.code}>{backupPath}</code>
+          </p>
+        )}
+      </section>
+
+      {/* Diagnostics section */}
+      <section style={s.section}>
+        <h2 style={s.sectionTitle}>Diagnostics</h2>
+        <p style={s.sectionDesc}>
+          Save a zip with app and OS versions, recent logs, database settings (secrets
+          redacted) and data folder sizes to attach to a bug report.
+        </p>
+        <button style={s.primaryBtn} onClick={handleExportDiagnostics} disabled={exporting}>
+          {exporting ? "Exporting…" : "Export diagnostics"}
+        </button>
+        {diagnosticsPath && (
+          <p style={s.savedPath}>
+            Saved to <code style={s.code}>{diagnosticsPath}</code>
+          </p>
+        )}
+      </section>
     </main>
   );
 }
@@ -273,6 +582,30 @@ const s: Record<string, React.CSSProperties> = {
     margin: "0 0 20px",
     lineHeight: 1.5,
   },
+  checkRow: {
+    display: "flex",
+    alignItems: "center",
+    gap: "8px",
+    fontSize: "13px",
+    color: "#2b2b2b",
+    cursor: "pointer",
+    marginBottom: "8px",
+  },
+  shortcutInput: {
+    width: "260px",
+    padding: "6px 8px",
+    fontSize: "13px",
+    fontFamily: "monospace",
+  },
+  timeInput: {
+    fontSize: "12px",
+    padding: "2px 4px",
+  },
+  savedPath: {
+    margin: "12px 0 0",
+    fontSize: "12px",
+    color: "#5e5e5e",
+  },
   code: {
     fontFamily: "'JetBrains Mono', monospace",
     fontSize: "12px",
@@ -3,8 +3,6 @@ import react from "@vitejs/plugin-react";
 
 // @ts-expect-error process is a nodejs global
 const host = process.env.TAURI_DEV_HOST;
-// @ts-expect-error process is a nodejs global
-const backendPort = process.env.VITE_BACKEND_PORT || "8000";
 
 // https://vite.dev/config/
 export default defineConfig(async () => ({
@@ -30,13 +28,5 @@ export default defineConfig(async () => ({
       // 3. tell Vite to ignore watching `src-tauri`
       ignored: ["**/src-tauri/**"],
     },
-    // 4. proxy /api → Python backend (avoids WebKitGTK sandbox issues)
-    proxy: {
-      "/api": {
-        target: `http://127.0.0.1:${backendPort}`,
-        changeOrigin: true,
-        rewrite: (path) => path.replace(/^\/api/, ""),
-      },
-    },
   },
 }));